@@ -0,0 +1,180 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::env;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use aoc2021::day10;
+use aoc2021::{ParseError, Puzzle};
+
+const USAGE: &str = "\
+usage:
+    aoc run <day> [--part <1|2>] [--input <path>]
+    aoc all [--inputs <dir>]
+    aoc check [--json] [--input <path>]
+    aoc list
+
+`run` reads the puzzle input from stdin unless --input is given.
+`all` runs every solved day, reading <dir>/day<N>.txt (default: inputs/)
+and skipping days without an input file.
+`check` reports the corrupted or unreadable lines of a day 10 input, read
+like `run` does, with --json printing one JSON object per line.";
+
+fn usage_error(msg: &str) -> ! {
+    eprintln!("error: {}\n\n{}", msg, USAGE);
+    process::exit(2);
+}
+
+fn read_input(path: Option<&Path>) -> io::Result<String> {
+    let mut input = String::new();
+    match path {
+        Some(path) => input = fs::read_to_string(path)?,
+        None => {
+            io::stdin().read_to_string(&mut input)?;
+        }
+    }
+    Ok(input)
+}
+
+fn run_parts(puzzle: &dyn Puzzle, part: Option<u32>, input: &str) -> Result<(), ParseError> {
+    for answer in puzzle.run(input, part)? {
+        println!("{}", answer);
+    }
+    Ok(())
+}
+
+fn cmd_run(mut args: impl Iterator<Item = String>) {
+    let day = args.next().unwrap_or_else(|| usage_error("missing day"));
+    let day: u32 = day
+        .parse()
+        .unwrap_or_else(|_| usage_error(&format!("invalid day '{}'", day)));
+    let puzzle =
+        aoc2021::puzzle(day).unwrap_or_else(|| usage_error(&format!("day {} is not solved", day)));
+
+    let mut part = None;
+    let mut path = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                part = match args.next().as_deref() {
+                    Some("1") => Some(1),
+                    Some("2") => Some(2),
+                    _ => usage_error("--part must be 1 or 2"),
+                }
+            }
+            "--input" => {
+                path = Some(PathBuf::from(
+                    args.next()
+                        .unwrap_or_else(|| usage_error("--input needs a path")),
+                ))
+            }
+            _ => usage_error(&format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    let input = read_input(path.as_deref()).unwrap_or_else(|e| {
+        eprintln!("error: could not read input: {}", e);
+        process::exit(1);
+    });
+    if let Err(e) = run_parts(puzzle, part, &input) {
+        let source = path.map_or("<stdin>".to_string(), |p| p.display().to_string());
+        eprintln!("error: invalid input in {}: {}", source, e);
+        process::exit(1);
+    }
+}
+
+fn cmd_all(mut args: impl Iterator<Item = String>) {
+    let mut dir = PathBuf::from("inputs");
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--inputs" => {
+                dir = PathBuf::from(
+                    args.next()
+                        .unwrap_or_else(|| usage_error("--inputs needs a directory")),
+                )
+            }
+            _ => usage_error(&format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    let mut failed = false;
+    for (day, puzzle) in aoc2021::puzzles() {
+        let path = dir.join(format!("day{}.txt", day));
+        let input = match read_input(Some(&path)) {
+            Ok(input) => input,
+            Err(_) => continue,
+        };
+        println!("day {}:", day);
+        if let Err(e) = run_parts(puzzle, None, &input) {
+            eprintln!("error: invalid input in {}: {}", path.display(), e);
+            failed = true;
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+fn cmd_check(mut args: impl Iterator<Item = String>) {
+    let mut json = false;
+    let mut path = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--input" => {
+                path = Some(PathBuf::from(
+                    args.next()
+                        .unwrap_or_else(|| usage_error("--input needs a path")),
+                ))
+            }
+            _ => usage_error(&format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    let input = read_input(path.as_deref()).unwrap_or_else(|e| {
+        eprintln!("error: could not read input: {}", e);
+        process::exit(1);
+    });
+    let diagnostics = day10::diagnostics(&input);
+    for diagnostic in diagnostics.iter() {
+        if json {
+            println!("{}", diagnostic.to_json());
+        } else {
+            println!("{}", diagnostic);
+        }
+    }
+    if !diagnostics.is_empty() {
+        process::exit(1);
+    }
+}
+
+fn main() {
+    let mut args = env::args().skip(1);
+    match args.next().as_deref() {
+        Some("run") => cmd_run(args),
+        Some("all") => cmd_all(args),
+        Some("check") => cmd_check(args),
+        Some("list") => {
+            for (day, _) in aoc2021::puzzles() {
+                println!("{}", day);
+            }
+        }
+        Some(cmd) => usage_error(&format!("unknown command '{}'", cmd)),
+        None => usage_error("missing command"),
+    }
+}
@@ -0,0 +1,112 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bit vectors, stored most significant bit first as `Vec<bool>`.
+
+use crate::parse::{Cursor, ParseError};
+
+/// Reads the rest of `line` as a string of '0' and '1' digits.
+pub fn parse_binary(line: &mut Cursor) -> Result<Vec<bool>, ParseError> {
+    let mut res = Vec::new();
+    while !line.is_empty() {
+        res.push(match line.peek() {
+            Some('0') => false,
+            Some('1') => true,
+            _ => return Err(line.error("'0' or '1'")),
+        });
+        line.next_char("a bit")?;
+    }
+    Ok(res)
+}
+
+/// Reads the rest of `line` as hexadecimal digits, four bits per digit.
+pub fn parse_hex(line: &mut Cursor) -> Result<Vec<bool>, ParseError> {
+    let mut res = Vec::new();
+    while !line.is_empty() {
+        let d = match line.peek().and_then(|c| c.to_digit(16)) {
+            Some(d) => d,
+            None => return Err(line.error("a hexadecimal digit")),
+        };
+        res.extend((0..4).rev().map(|i| d & (1 << i) != 0));
+        line.next_char("a hexadecimal digit")?;
+    }
+    Ok(res)
+}
+
+/// Interprets up to 64 bits as an unsigned integer.
+pub fn to_int(num: &[bool]) -> u64 {
+    let mut i = 0;
+    for d in num.iter() {
+        i <<= 1;
+        if *d {
+            i += 1;
+        }
+    }
+    i
+}
+
+/// Reads fields of a bit vector in order.
+#[derive(Clone, Debug)]
+pub struct BitReader<'a> {
+    bits: &'a [bool],
+    pos: usize,
+}
+
+impl<'a> BitReader<'a> {
+    pub fn new(bits: &'a [bool]) -> BitReader<'a> {
+        BitReader { bits, pos: 0 }
+    }
+
+    /// The number of bits read so far.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.bits.len() - self.pos
+    }
+
+    pub fn read_bit(&mut self) -> Option<bool> {
+        let bit = *self.bits.get(self.pos)?;
+        self.pos += 1;
+        Some(bit)
+    }
+
+    /// Reads an `n`-bit unsigned integer, or returns `None` without
+    /// consuming anything if fewer than `n` bits are left.
+    pub fn read(&mut self, n: usize) -> Option<u64> {
+        assert!(n <= 64);
+        let bits = self.bits.get(self.pos..self.pos + n)?;
+        self.pos += n;
+        Some(to_int(bits))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reader() {
+        let bits = parse_hex(&mut Cursor::new(1, "D2FE28")).unwrap();
+        let mut reader = BitReader::new(&bits);
+        assert_eq!(Some(6), reader.read(3));
+        assert_eq!(Some(4), reader.read(3));
+        assert_eq!(Some(true), reader.read_bit());
+        assert_eq!(Some(0b0111), reader.read(4));
+        assert_eq!(13, reader.remaining());
+        assert_eq!(None, reader.read(14));
+        assert_eq!(11, reader.pos());
+    }
+}
@@ -0,0 +1,92 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Axis-aligned boxes of integer cells in three dimensions.
+
+use std::fmt;
+
+use num::PrimInt;
+
+/// The cells from `min` to `max` on every axis, inclusive. A cuboid always
+/// contains at least one cell.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Cuboid<T> {
+    min: [T; 3],
+    max: [T; 3],
+}
+
+impl<T: PrimInt> Cuboid<T> {
+    /// Returns `None` if `min` is above `max` on any axis.
+    pub fn new(min: [T; 3], max: [T; 3]) -> Option<Cuboid<T>> {
+        (0..3)
+            .all(|i| min[i] <= max[i])
+            .then_some(Cuboid { min, max })
+    }
+
+    pub fn min(&self) -> [T; 3] {
+        self.min
+    }
+
+    pub fn max(&self) -> [T; 3] {
+        self.max
+    }
+
+    /// The number of cells in the cuboid.
+    pub fn volume(&self) -> T {
+        (0..3).fold(T::one(), |v, i| v * (self.max[i] - self.min[i] + T::one()))
+    }
+
+    pub fn contains(&self, p: [T; 3]) -> bool {
+        (0..3).all(|i| self.min[i] <= p[i] && p[i] <= self.max[i])
+    }
+
+    /// The cells in both cuboids, if there are any.
+    pub fn intersection(&self, other: &Cuboid<T>) -> Option<Cuboid<T>> {
+        let min = [0, 1, 2].map(|i| self.min[i].max(other.min[i]));
+        let max = [0, 1, 2].map(|i| self.max[i].min(other.max[i]));
+        Cuboid::new(min, max)
+    }
+}
+
+/// Prints the cuboid as in `x=-1..1,y=0..2,z=5..5`.
+impl<T: fmt::Display> fmt::Display for Cuboid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "x={}..{},y={}..{},z={}..{}",
+            self.min[0], self.max[0], self.min[1], self.max[1], self.min[2], self.max[2]
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intersection() {
+        let a = Cuboid::new([0, 0, 0], [3, 3, 3]).unwrap();
+        let b = Cuboid::new([2, -1, 3], [5, 1, 4]).unwrap();
+        assert_eq!(64, a.volume());
+        let i = a.intersection(&b).unwrap();
+        assert_eq!("x=2..3,y=0..1,z=3..3", i.to_string());
+        assert_eq!(4, i.volume());
+        assert!(i.contains([3, 1, 3]));
+        assert_eq!(
+            None,
+            a.intersection(&Cuboid::new([4, 0, 0], [4, 0, 0]).unwrap())
+        );
+        assert_eq!(None, Cuboid::new([0, 1, 0], [0, 0, 0]));
+    }
+}
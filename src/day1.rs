@@ -0,0 +1,66 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use itertools::Itertools;
+
+use crate::parse::{self, ParseError};
+use crate::Solution;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<u32>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<u32>, ParseError> {
+        parse::lines(input)
+            .map(|mut l| {
+                let n = l.number()?;
+                l.end()?;
+                Ok(n)
+            })
+            .collect()
+    }
+
+    fn part1(nums: &Vec<u32>) -> usize {
+        nums.iter().tuple_windows().filter(|(a, b)| a < b).count()
+    }
+
+    fn part2(nums: &Vec<u32>) -> usize {
+        nums.iter()
+            .tuple_windows()
+            .map(|(x, y, z)| x + y + z)
+            .tuple_windows()
+            .filter(|(a, b)| a < b)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: [u32; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(7, Day1::part1(&INPUT.to_vec()));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(5, Day1::part2(&INPUT.to_vec()));
+    }
+}
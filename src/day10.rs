@@ -0,0 +1,546 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::iter::Iterator;
+use std::vec::Vec;
+
+use crate::parse::{self, Cursor, ParseError};
+use crate::Solution;
+
+/// A pair of opening and closing delimiters, which can be several
+/// characters long, with the points for a corrupted line whose first
+/// illegal token is the closing delimiter, and for each closing delimiter
+/// a completion needs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pair {
+    pub open: String,
+    pub close: String,
+    pub corrupted: u64,
+    pub completion: u64,
+}
+
+impl Pair {
+    pub fn new(open: &str, close: &str, corrupted: u64, completion: u64) -> Pair {
+        Pair {
+            open: open.to_owned(),
+            close: close.to_owned(),
+            corrupted,
+            completion,
+        }
+    }
+}
+
+/// A stretch of a line in which delimiters are ignored, such as a string
+/// or a comment. Without an end, it runs to the end of the line.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Region {
+    pub start: String,
+    pub end: Option<String>,
+}
+
+impl Region {
+    pub fn new(start: &str, end: Option<&str>) -> Region {
+        Region {
+            start: start.to_owned(),
+            end: end.map(str::to_owned),
+        }
+    }
+}
+
+/// The delimiters a checker knows about. The default is the four bracket
+/// pairs of the puzzle, with no regions, and anything else is an error.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DelimiterSet {
+    pub pairs: Vec<Pair>,
+    pub regions: Vec<Region>,
+    /// Whether text that is not a delimiter is skipped rather than an
+    /// error.
+    pub allow_other: bool,
+}
+
+impl Default for DelimiterSet {
+    fn default() -> DelimiterSet {
+        DelimiterSet {
+            pairs: vec![
+                Pair::new("(", ")", 3, 1),
+                Pair::new("[", "]", 57, 2),
+                Pair::new("{", "}", 1197, 3),
+                Pair::new("<", ">", 25137, 4),
+            ],
+            regions: Vec::new(),
+            allow_other: false,
+        }
+    }
+}
+
+/// How to repair a closing delimiter that does not match the innermost
+/// open one.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Fix {
+    /// Leave it out.
+    Drop,
+    /// Put the expected closing delimiter in its place.
+    Replace,
+}
+
+enum Token<'a> {
+    Open(&'a Pair),
+    Close(&'a Pair),
+    Region(&'a Region),
+}
+
+impl DelimiterSet {
+    /// The longest token at the start of `rest`. On a tie, region starts
+    /// win over openers, which win over closers.
+    fn token(&self, rest: &str) -> Option<Token<'_>> {
+        let regions = self.regions.iter().map(|r| (&r.start, Token::Region(r)));
+        let opens = self.pairs.iter().map(|p| (&p.open, Token::Open(p)));
+        let closes = self.pairs.iter().map(|p| (&p.close, Token::Close(p)));
+        let mut best: Option<(usize, Token)> = None;
+        for (text, token) in regions.chain(opens).chain(closes) {
+            let longer = best.as_ref().is_none_or(|(len, _)| text.len() > *len);
+            if rest.starts_with(text.as_str()) && longer {
+                best = Some((text.len(), token));
+            }
+        }
+        best.map(|(_, token)| token)
+    }
+
+    /// Checks a line up to its first illegal token.
+    pub fn check(&self, line: Cursor) -> Result<Score, ParseError> {
+        self.scan(line, None, &mut String::new())
+    }
+
+    /// Repairs a line so that it is complete: every illegal closing
+    /// delimiter is fixed as given, and then the completion is appended.
+    /// A closing delimiter with nothing to close is always dropped.
+    pub fn repair(&self, line: Cursor, fix: Fix) -> Result<String, ParseError> {
+        let mut repaired = String::new();
+        match self.scan(line, Some(fix), &mut repaired)? {
+            Incomplete { completion, .. } => repaired.push_str(&completion),
+            Corrupted { .. } => unreachable!(),
+        }
+        Ok(repaired)
+    }
+
+    /// Checks a line, copying the text it has checked to `out`. With a
+    /// fix, corruption is repaired and checking goes on.
+    fn scan(
+        &self,
+        mut line: Cursor,
+        fix: Option<Fix>,
+        out: &mut String,
+    ) -> Result<Score, ParseError> {
+        let mut stack: Vec<(&Pair, usize)> = Vec::new();
+        let mut unterminated = None;
+        while !line.is_empty() {
+            let column = line.column();
+            let rest = line.rest();
+            match self.token(rest) {
+                Some(Token::Open(p)) => {
+                    line.literal(&p.open)?;
+                    stack.push((p, column));
+                }
+                Some(Token::Close(p)) => {
+                    let expected = stack.pop();
+                    if expected.map(|(e, _)| &e.close) != Some(&p.close) {
+                        line.literal(&p.close)?;
+                        match (fix, expected) {
+                            (None, _) => {
+                                return Ok(Corrupted {
+                                    column,
+                                    found: p.close.clone(),
+                                    expected: expected.map(|(e, _)| e.close.clone()),
+                                    open: expected.map(|(e, col)| (col, e.open.clone())),
+                                    points: p.corrupted,
+                                })
+                            }
+                            (Some(Fix::Replace), Some((e, _))) => out.push_str(&e.close),
+                            (Some(Fix::Drop), Some(e)) => stack.push(e),
+                            (Some(_), None) => (),
+                        }
+                        continue;
+                    }
+                    line.literal(&p.close)?;
+                }
+                Some(Token::Region(r)) => {
+                    line.literal(&r.start)?;
+                    match &r.end {
+                        Some(end) => {
+                            if !line.skip_past(end) {
+                                unterminated = Some(end);
+                            }
+                        }
+                        None => {
+                            line.take_while(|_| true);
+                        }
+                    }
+                }
+                None if self.allow_other => {
+                    line.next_char("a character")?;
+                }
+                None => return Err(line.error("a delimiter")),
+            }
+            out.push_str(&rest[..rest.len() - line.rest().len()]);
+        }
+
+        let mut completion = unterminated.cloned().unwrap_or_default();
+        let mut points = 0;
+        for (p, _) in stack.iter().rev() {
+            completion.push_str(&p.close);
+            points = points * 5 + p.completion;
+        }
+        Ok(Incomplete { completion, points })
+    }
+}
+
+/// The result of checking a line.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Score {
+    /// The first illegal token: a closing delimiter that does not match
+    /// the innermost open one, or that has nothing to close. The column is
+    /// 1-based. `open` is the column and text of the unmatched opening
+    /// delimiter, if any.
+    Corrupted {
+        column: usize,
+        found: String,
+        expected: Option<String>,
+        open: Option<(usize, String)>,
+        points: u64,
+    },
+    /// Every delimiter so far matches. The completion ends the region the
+    /// line stops in, if any, and closes the delimiters still open. It is
+    /// empty if the line is complete.
+    Incomplete { completion: String, points: u64 },
+}
+
+use Score::*;
+
+impl Score {
+    pub fn points(&self) -> u64 {
+        match self {
+            Corrupted { points, .. } | Incomplete { points, .. } => *points,
+        }
+    }
+}
+
+/// Checks a line with the default delimiters.
+pub fn score(line: Cursor) -> Result<Score, ParseError> {
+    DelimiterSet::default().check(line)
+}
+
+/// A secondary message attached to a [`Diagnostic`], on the same line.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Note {
+    pub column: usize,
+    pub message: String,
+}
+
+/// A compiler-style report of a corrupted line. Lines and columns are
+/// 1-based.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Diagnostic {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+    /// The full text of the line.
+    pub text: String,
+    pub note: Option<Note>,
+}
+
+impl Diagnostic {
+    /// Reports the first illegal token of a line, or `None` if the line is
+    /// not corrupted.
+    pub fn new(line: &Cursor, score: &Score) -> Option<Diagnostic> {
+        let Corrupted {
+            column,
+            found,
+            expected,
+            open,
+            ..
+        } = score
+        else {
+            return None;
+        };
+        let message = match expected {
+            Some(expected) => format!("expected '{}' but found '{}'", expected, found),
+            None => format!("found '{}' with nothing to close", found),
+        };
+        Some(Diagnostic {
+            line: line.line(),
+            column: *column,
+            message,
+            text: line.text().to_owned(),
+            note: open.as_ref().map(|(column, open)| Note {
+                column: *column,
+                message: format!("unmatched '{}' opened here", open),
+            }),
+        })
+    }
+
+    /// Reports a line that could not be checked, such as one with a
+    /// character that is not a delimiter.
+    pub fn from_error(err: &ParseError) -> Diagnostic {
+        let found = err.text.chars().nth(err.column - 1);
+        let message = match found {
+            Some(c) => format!("expected {} but found '{}'", err.expected, c),
+            None => format!("expected {} but found end of line", err.expected),
+        };
+        Diagnostic {
+            line: err.line,
+            column: err.column,
+            message,
+            text: err.text.clone(),
+            note: None,
+        }
+    }
+
+    /// Formats the diagnostic as a single-line JSON object, with a null
+    /// note if there is none.
+    pub fn to_json(&self) -> String {
+        let note = match &self.note {
+            Some(note) => format!(
+                "{{\"column\":{},\"message\":{}}}",
+                note.column,
+                json_string(&note.message)
+            ),
+            None => "null".to_owned(),
+        };
+        format!(
+            "{{\"line\":{},\"column\":{},\"message\":{},\"text\":{},\"note\":{}}}",
+            self.line,
+            self.column,
+            json_string(&self.message),
+            json_string(&self.text),
+            note
+        )
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}:{}: {}", self.line, self.column, self.message)?;
+        writeln!(f, "    {}", self.text)?;
+        write!(f, "    {:>1$}", "^", self.column)?;
+        if let Some(note) = &self.note {
+            writeln!(f)?;
+            writeln!(f, "{}:{}: note: {}", self.line, note.column, note.message)?;
+            writeln!(f, "    {}", self.text)?;
+            write!(f, "    {:>1$}", "^", note.column)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reports every corrupted or unreadable line of `input`, checked with the
+/// default delimiters.
+pub fn diagnostics(input: &str) -> Vec<Diagnostic> {
+    let set = DelimiterSet::default();
+    let mut diagnostics = Vec::new();
+    for line in parse::lines(input) {
+        match set.check(line.clone()) {
+            Ok(score) => diagnostics.extend(Diagnostic::new(&line, &score)),
+            Err(err) => diagnostics.push(Diagnostic::from_error(&err)),
+        }
+    }
+    diagnostics
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<Score>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<Score>, ParseError> {
+        let set = DelimiterSet::default();
+        parse::lines(input).map(|line| set.check(line)).collect()
+    }
+
+    fn part1(scores: &Vec<Score>) -> u64 {
+        scores
+            .iter()
+            .filter(|s| matches!(s, Corrupted { .. }))
+            .map(Score::points)
+            .sum()
+    }
+
+    /// The middle score of the incomplete lines, or 0 if there are none.
+    fn part2(scores: &Vec<Score>) -> u64 {
+        let mut points: Vec<u64> = scores
+            .iter()
+            .filter(|s| matches!(s, Incomplete { .. }))
+            .map(Score::points)
+            .collect();
+        points.sort_unstable();
+        points.get(points.len() / 2).copied().unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = "\
+[({(<(())[]>[[{[]{<()<>>
+[(()[<>])]({[<{<<[]>>(
+{([(<{}[<>[]}>{[]{[(<()>
+(((({<>}<{<{<>}{[]{[]{}
+[[<[([]))<([[{}[[()]]]
+[{[{({}]{}}([{[{{{}}([]
+{<[[]]>}<{[{[{[]{()[[[]
+[<(<(<(<{}))><([]([]()
+<{([([[(<>()){}]>(<<{{
+<{([{{}}[<[[[<>{}]]]>[]]
+";
+
+    #[test]
+    fn test_score() {
+        let check = |s| score(Cursor::new(1, s));
+        assert_eq!(
+            Ok(Corrupted {
+                column: 13,
+                found: "}".to_owned(),
+                expected: Some("]".to_owned()),
+                open: Some((8, "[".to_owned())),
+                points: 1197
+            }),
+            check("{([(<{}[<>[]}>{[]{[(<()>")
+        );
+        assert_eq!(
+            Ok(Corrupted {
+                column: 1,
+                found: ")".to_owned(),
+                expected: None,
+                open: None,
+                points: 3
+            }),
+            check(")(")
+        );
+        assert_eq!(
+            Ok(Incomplete {
+                completion: "}}]])})]".to_owned(),
+                points: 288957
+            }),
+            check("[({(<(())[]>[[{[]{<()<>>")
+        );
+
+        let err = check("(a)").unwrap_err();
+        assert_eq!((1, 2), (err.line, err.column));
+    }
+
+    #[test]
+    fn test_delimiter_set() {
+        let mut set = DelimiterSet::default();
+        set.pairs.push(Pair::new("«", "»", 5, 5));
+        set.pairs.push(Pair::new("/*", "*/", 7, 7));
+        set.regions.push(Region::new("\"", Some("\"")));
+        set.regions.push(Region::new("//", None));
+        set.allow_other = true;
+        let check = |s| set.check(Cursor::new(1, s)).unwrap();
+
+        assert_eq!(
+            Incomplete {
+                completion: "*/]".to_owned(),
+                points: 7 * 5 + 2
+            },
+            check("f[«x» /* \")]\" // )")
+        );
+        assert_eq!(
+            Incomplete {
+                completion: "\")".to_owned(),
+                points: 1
+            },
+            check("(\"[")
+        );
+        assert_eq!(
+            Corrupted {
+                column: 3,
+                found: "*/".to_owned(),
+                expected: Some("»".to_owned()),
+                open: Some((1, "«".to_owned())),
+                points: 7
+            },
+            check("«a*/")
+        );
+    }
+
+    #[test]
+    fn test_repair() {
+        let set = DelimiterSet::default();
+        let repair = |s, fix| set.repair(Cursor::new(1, s), fix).unwrap();
+        assert_eq!(
+            "[({(<(())[]>[[{[]{<()<>>}}]])})]",
+            repair("[({(<(())[]>[[{[]{<()<>>", Fix::Drop)
+        );
+        assert_eq!("{([(<{}[<>[]]>)])}", repair("{([(<{}[<>[]}>", Fix::Replace));
+        assert_eq!("()<>", repair("(]<>", Fix::Replace));
+        assert_eq!("(<>)", repair("(]<>", Fix::Drop));
+        assert_eq!("()", repair("))(", Fix::Replace));
+        assert_eq!("<[]>", repair("<[]>", Fix::Drop));
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let diagnostics = diagnostics("<>\n{([(<{}[<>[]}>\n)(\n(a)\n");
+        assert_eq!(3, diagnostics.len());
+        assert_eq!(
+            "\
+2:13: expected ']' but found '}'
+    {([(<{}[<>[]}>
+                ^
+2:8: note: unmatched '[' opened here
+    {([(<{}[<>[]}>
+           ^",
+            diagnostics[0].to_string()
+        );
+        assert_eq!(
+            r#"{"line":3,"column":1,"message":"found ')' with nothing to close","text":")(","note":null}"#,
+            diagnostics[1].to_json()
+        );
+        assert_eq!(
+            r#"{"line":4,"column":2,"message":"expected a delimiter but found 'a'","text":"(a)","note":null}"#,
+            diagnostics[2].to_json()
+        );
+        assert_eq!("\"a\\\"b\\\\\\u0007\"", json_string("a\"b\\\u{7}"));
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(26397, Day10::part1(&Day10::parse(INPUT).unwrap()));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(288957, Day10::part2(&Day10::parse(INPUT).unwrap()));
+        assert_eq!(0, Day10::part2(&Day10::parse("(]\n").unwrap()));
+    }
+}
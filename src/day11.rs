@@ -0,0 +1,156 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::iter::Iterator;
+use std::vec::Vec;
+
+use crate::grid::{Coord, Grid};
+use crate::parse::ParseError;
+use crate::Solution;
+
+/// Advances the octopuses by one step and returns how many flashed.
+pub fn step(energy: &mut Grid<u8>) -> usize {
+    let mut flashed = Grid::new(energy.rows(), energy.cols(), false);
+    let mut pending: Vec<Coord> = Vec::new();
+    for (pos, e) in energy.iter_mut() {
+        *e += 1;
+        if *e > 9 {
+            pending.push(pos);
+        }
+    }
+
+    let mut flashes = 0;
+    while let Some(pos) = pending.pop() {
+        if flashed[pos] {
+            continue;
+        }
+        flashed[pos] = true;
+        flashes += 1;
+
+        let neighbors: Vec<Coord> = energy.neighbors8(pos).map(|(n, _)| n).collect();
+        for n in neighbors {
+            energy[n] += 1;
+            if energy[n] > 9 && !flashed[n] {
+                pending.push(n);
+            }
+        }
+    }
+
+    for (pos, e) in energy.iter_mut() {
+        if flashed[pos] {
+            *e = 0;
+        }
+    }
+    flashes
+}
+
+/// The state of the octopuses after a step.
+#[derive(Clone, Debug)]
+pub struct Step {
+    pub energy: Grid<u8>,
+    pub flashes: usize,
+}
+
+/// Iterator over the successive states of the octopuses, starting with
+/// the state after the first step.
+pub struct Steps {
+    energy: Grid<u8>,
+}
+
+impl Iterator for Steps {
+    type Item = Step;
+
+    fn next(&mut self) -> Option<Step> {
+        let flashes = step(&mut self.energy);
+        Some(Step {
+            energy: self.energy.clone(),
+            flashes,
+        })
+    }
+}
+
+pub fn steps(energy: &Grid<u8>) -> Steps {
+    Steps {
+        energy: energy.clone(),
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Grid<u8>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+        Grid::parse_digits(input)
+    }
+
+    fn part1(energy: &Grid<u8>) -> usize {
+        steps(energy).take(100).map(|s| s.flashes).sum()
+    }
+
+    fn part2(energy: &Grid<u8>) -> usize {
+        let all = energy.rows() * energy.cols();
+        1 + steps(energy).position(|s| s.flashes == all).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = "\
+5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526
+";
+
+    #[test]
+    fn test_steps() {
+        let energy = Grid::parse_digits("11111\n19991\n19191\n19991\n11111\n").unwrap();
+        let states: Vec<_> = steps(&energy).take(2).collect();
+        assert_eq!(9, states[0].flashes);
+        assert_eq!(
+            "34543\n40004\n50005\n40004\n34543\n",
+            states[0].energy.to_string()
+        );
+        assert_eq!(0, states[1].flashes);
+        assert_eq!(
+            "45654\n51115\n61116\n51115\n45654\n",
+            states[1].energy.to_string()
+        );
+
+        let energy = Day11::parse(INPUT).unwrap();
+        let flashes: usize = steps(&energy).take(10).map(|s| s.flashes).sum();
+        assert_eq!(204, flashes);
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(1656, Day11::part1(&Day11::parse(INPUT).unwrap()));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(195, Day11::part2(&Day11::parse(INPUT).unwrap()));
+    }
+}
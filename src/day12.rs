@@ -0,0 +1,229 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::iter::Iterator;
+
+use multimap::MultiMap;
+
+use crate::parse::{self, Cursor, ParseError};
+use crate::Solution;
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Cave(String);
+
+impl Cave {
+    fn is_small(&self) -> bool {
+        self.0.chars().next().unwrap().is_lowercase()
+    }
+}
+
+impl fmt::Display for Cave {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+fn read_cave(line: &mut Cursor) -> Result<Cave, ParseError> {
+    let name = line.take_while(|c| c.is_ascii_alphabetic());
+    if name.is_empty() {
+        return Err(line.error("a cave name"));
+    }
+    Ok(Cave(name.to_string()))
+}
+
+fn read_graph<'a>(
+    lines: impl Iterator<Item = Cursor<'a>>,
+) -> Result<MultiMap<Cave, Cave>, ParseError> {
+    let mut map = MultiMap::new();
+    for mut l in lines {
+        let c1 = read_cave(&mut l)?;
+        l.literal("-")?;
+        let c2 = read_cave(&mut l)?;
+        l.end()?;
+        map.insert(c1.clone(), c2.clone());
+        map.insert(c2, c1);
+    }
+    Ok(map)
+}
+
+/// A cave on the current path, and how far its neighbours have been tried.
+struct Frame<'a> {
+    cave: &'a Cave,
+    next: usize,
+    /// Whether entering this cave used up the one second visit.
+    twice: bool,
+}
+
+/// Iterator over the paths from `start` to `end`, found depth first. Small
+/// caves are visited at most once, except that with a second visit allowed
+/// one of them other than `start` may be visited twice.
+pub struct Paths<'a> {
+    map: &'a MultiMap<Cave, Cave>,
+    stack: Vec<Frame<'a>>,
+    visits: HashMap<&'a Cave, usize>,
+    can_visit_twice: bool,
+}
+
+impl<'a> Paths<'a> {
+    /// Moves on to the next path, leaving it on the stack, and returns
+    /// whether there was one.
+    fn advance(&mut self) -> bool {
+        while let Some(top) = self.stack.last_mut() {
+            let neighbours = match top.cave.0.as_str() {
+                "end" => &[][..],
+                _ => self.map.get_vec(top.cave).unwrap(),
+            };
+            let Some(dst) = neighbours.get(top.next) else {
+                let frame = self.stack.pop().unwrap();
+                *self.visits.get_mut(frame.cave).unwrap() -= 1;
+                if frame.twice {
+                    self.can_visit_twice = true;
+                }
+                continue;
+            };
+            top.next += 1;
+
+            let visits = self.visits.entry(dst).or_insert(0);
+            let mut twice = false;
+            if dst.is_small() && *visits > 0 {
+                if dst.0 == "start" || !self.can_visit_twice {
+                    continue;
+                }
+                self.can_visit_twice = false;
+                twice = true;
+            }
+            *visits += 1;
+            self.stack.push(Frame {
+                cave: dst,
+                next: 0,
+                twice,
+            });
+            if dst.0 == "end" {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+impl<'a> Iterator for Paths<'a> {
+    type Item = Vec<Cave>;
+
+    fn next(&mut self) -> Option<Vec<Cave>> {
+        if self.advance() {
+            Some(self.stack.iter().map(|f| f.cave.clone()).collect())
+        } else {
+            None
+        }
+    }
+
+    /// Counts the remaining paths without building them.
+    fn count(mut self) -> usize {
+        let mut count = 0;
+        while self.advance() {
+            count += 1;
+        }
+        count
+    }
+}
+
+pub fn paths(map: &MultiMap<Cave, Cave>, can_visit_twice: bool) -> Paths<'_> {
+    let (start, _) = map.iter().find(|(c, _)| c.0 == "start").unwrap();
+    Paths {
+        map,
+        stack: vec![Frame {
+            cave: start,
+            next: 0,
+            twice: false,
+        }],
+        visits: HashMap::from([(start, 1)]),
+        can_visit_twice,
+    }
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = MultiMap<Cave, Cave>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<MultiMap<Cave, Cave>, ParseError> {
+        let map = read_graph(parse::lines(input))?;
+        if !map.contains_key(&Cave("start".to_string())) {
+            return Err(parse::eof_error(input, "a path from 'start'"));
+        }
+        Ok(map)
+    }
+
+    fn part1(map: &MultiMap<Cave, Cave>) -> usize {
+        paths(map, false).count()
+    }
+
+    fn part2(map: &MultiMap<Cave, Cave>) -> usize {
+        paths(map, true).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = "\
+start-A
+start-b
+A-c
+A-b
+b-d
+A-end
+b-end
+";
+
+    #[test]
+    fn test_paths() {
+        let map = Day12::parse(INPUT).unwrap();
+        let mut paths: Vec<String> = paths(&map, false)
+            .map(|p| p.iter().map(Cave::to_string).collect::<Vec<_>>().join(","))
+            .collect();
+        paths.sort();
+        assert_eq!(
+            vec![
+                "start,A,b,A,c,A,end",
+                "start,A,b,A,end",
+                "start,A,b,end",
+                "start,A,c,A,b,A,end",
+                "start,A,c,A,b,end",
+                "start,A,c,A,end",
+                "start,A,end",
+                "start,b,A,c,A,end",
+                "start,b,A,end",
+                "start,b,end",
+            ],
+            paths
+        );
+        assert_eq!(36, super::paths(&map, true).collect::<Vec<_>>().len());
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(10, Day12::part1(&Day12::parse(INPUT).unwrap()));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(36, Day12::part2(&Day12::parse(INPUT).unwrap()));
+    }
+}
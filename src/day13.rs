@@ -0,0 +1,337 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::iter::Iterator;
+use std::vec::Vec;
+
+use crate::grid::Grid;
+use crate::parse::{self, Cursor, ParseError};
+use crate::Solution;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Fold {
+    /// Fold the right half leftwards along the vertical line at this x.
+    X(u32),
+    /// Fold the bottom half upwards along the horizontal line at this y.
+    Y(u32),
+}
+
+/// Where `v` ends up after folding along `line`, which must be at least
+/// half of `v`.
+fn mirror(v: u32, line: u32) -> u32 {
+    if v > line {
+        line - (v - line)
+    } else {
+        v
+    }
+}
+
+/// The dots on a sheet of transparent paper, as `(x, y)` pairs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Sheet {
+    dots: HashSet<(u32, u32)>,
+}
+
+impl Sheet {
+    pub fn len(&self) -> usize {
+        self.dots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dots.is_empty()
+    }
+
+    pub fn fold(&self, fold: Fold) -> Sheet {
+        let dots = self
+            .dots
+            .iter()
+            .map(|&(x, y)| match fold {
+                Fold::X(line) => (mirror(x, line), y),
+                Fold::Y(line) => (x, mirror(y, line)),
+            })
+            .collect();
+        Sheet { dots }
+    }
+
+    /// Draws the sheet with `#` for dots and `.` for empty paper.
+    pub fn render(&self) -> Grid<char> {
+        let cols = self.dots.iter().map(|d| d.0 + 1).max().unwrap_or(0);
+        let rows = self.dots.iter().map(|d| d.1 + 1).max().unwrap_or(0);
+        let mut image = Grid::new(rows as usize, cols as usize, '.');
+        for &(x, y) in self.dots.iter() {
+            image[(y as usize, x as usize)] = '#';
+        }
+        image
+    }
+}
+
+const GLYPH_WIDTH: usize = 4;
+const GLYPH_HEIGHT: usize = 6;
+
+/// The capital letters used in puzzle answers, each drawn in a 4x6 cell.
+const GLYPHS: [(char, &str); 18] = [
+    ('A', ".##.#..##..######..##..#"),
+    ('B', "###.#..####.#..##..####."),
+    ('C', ".##.#..##...#...#..#.##."),
+    ('E', "#####...###.#...#...####"),
+    ('F', "#####...###.#...#...#..."),
+    ('G', ".##.#..##...#.###..#.###"),
+    ('H', "#..##..######..##..##..#"),
+    ('I', ".###..#...#...#...#..###"),
+    ('J', "..##...#...#...##..#.##."),
+    ('K', "#..##.#.##..#.#.#.#.#..#"),
+    ('L', "#...#...#...#...#...####"),
+    ('O', ".##.#..##..##..##..#.##."),
+    ('P', "###.#..##..####.#...#..."),
+    ('R', "###.#..##..####.#.#.#..#"),
+    ('S', ".####...#....##....####."),
+    ('U', "#..##..##..##..##..#.##."),
+    ('Y', "#...#....#.#..#...#...#."),
+    ('Z', "####...#..#..#..#...####"),
+];
+
+/// Reads the capital letters drawn in `image`, which are 4x6 glyphs
+/// separated by one empty column. Returns `None` if any glyph is not
+/// recognized.
+pub fn ocr(image: &Grid<char>) -> Option<String> {
+    if image.rows() == 0 || image.rows() > GLYPH_HEIGHT {
+        return None;
+    }
+    (0..image.cols())
+        .step_by(GLYPH_WIDTH + 1)
+        .map(|left| {
+            let mut glyph = String::new();
+            for r in 0..GLYPH_HEIGHT {
+                for c in left..left + GLYPH_WIDTH {
+                    glyph.push(*image.get((r, c)).unwrap_or(&'.'));
+                }
+            }
+            GLYPHS
+                .iter()
+                .find(|(_, g)| *g == glyph)
+                .map(|(letter, _)| *letter)
+        })
+        .collect()
+}
+
+/// The instructions from the manual: the dots of the first page and the
+/// folds to make.
+#[derive(Clone, Debug)]
+pub struct Manual {
+    pub sheet: Sheet,
+    pub folds: Vec<Fold>,
+}
+
+fn read_dot(mut line: Cursor) -> Result<(u32, u32), ParseError> {
+    let x = line.number()?;
+    line.literal(",")?;
+    let y = line.number()?;
+    line.end()?;
+    Ok((x, y))
+}
+
+fn read_fold(mut line: Cursor) -> Result<Fold, ParseError> {
+    line.literal("fold along ")?;
+    let axis = match line.peek() {
+        Some('x') => Fold::X,
+        Some('y') => Fold::Y,
+        _ => return Err(line.error("'x' or 'y'")),
+    };
+    line.next_char("an axis")?;
+    line.literal("=")?;
+    let fold = axis(line.number()?);
+    line.end()?;
+    Ok(fold)
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Manual;
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Manual, ParseError> {
+        let mut lines = parse::lines(input);
+        let mut dots = HashSet::new();
+        for line in lines.by_ref() {
+            if line.is_empty() {
+                break;
+            }
+            dots.insert(read_dot(line)?);
+        }
+        if dots.is_empty() {
+            let err = match parse::lines(input).next() {
+                Some(line) => line.error("a dot"),
+                None => parse::eof_error(input, "a dot"),
+            };
+            return Err(err);
+        }
+        // Folding only moves dots along one axis, so the coordinates on
+        // each axis can be folded on their own to check that no dot ends up
+        // folded past the edge of the paper.
+        let mut xs: HashSet<u32> = dots.iter().map(|d| d.0).collect();
+        let mut ys: HashSet<u32> = dots.iter().map(|d| d.1).collect();
+        let mut folds = Vec::new();
+        for line in lines {
+            let fold = read_fold(line.clone())?;
+            let (values, at) = match fold {
+                Fold::X(at) => (&mut xs, at),
+                Fold::Y(at) => (&mut ys, at),
+            };
+            let max = values.iter().copied().max().unwrap_or(0);
+            if max - max.min(at) > at {
+                let column = "fold along x=".len() + 1;
+                return Err(
+                    line.error_at(column, format!("a line of at least {}", max.div_ceil(2)))
+                );
+            }
+            *values = values.iter().map(|&v| mirror(v, at)).collect();
+            folds.push(fold);
+        }
+        Ok(Manual {
+            sheet: Sheet { dots },
+            folds,
+        })
+    }
+
+    fn part1(manual: &Manual) -> usize {
+        match manual.folds.first() {
+            Some(fold) => manual.sheet.fold(*fold).len(),
+            None => manual.sheet.len(),
+        }
+    }
+
+    /// Reads the code from the folded sheet, falling back to the picture
+    /// itself if it does not consist of known letters.
+    fn part2(manual: &Manual) -> String {
+        let sheet = manual
+            .folds
+            .iter()
+            .fold(manual.sheet.clone(), |sheet, fold| sheet.fold(*fold));
+        let image = sheet.render();
+        ocr(&image).unwrap_or_else(|| format!("\n{}", image))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = "\
+6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0
+
+fold along y=7
+fold along x=5
+";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(17, Day13::part1(&Day13::parse(INPUT).unwrap()));
+    }
+
+    #[test]
+    fn test_parse() {
+        let err = Day13::parse("1,20\n\nfold along y=5\n").unwrap_err();
+        assert_eq!((3, 14), (err.line, err.column));
+        assert_eq!("a line of at least 10", err.expected);
+
+        let err = Day13::parse("1,20\n\nfold along y=12\nfold along y=1\n").unwrap_err();
+        assert_eq!(4, err.line);
+
+        let err = Day13::parse("\nfold along x=1\n").unwrap_err();
+        assert_eq!(
+            (1, 1, "a dot"),
+            (err.line, err.column, err.expected.as_str())
+        );
+        let err = Day13::parse("").unwrap_err();
+        assert_eq!((1, "a dot"), (err.line, err.expected.as_str()));
+    }
+
+    #[test]
+    fn test_render() {
+        let manual = Day13::parse(INPUT).unwrap();
+        let sheet = manual.sheet.fold(manual.folds[0]).fold(manual.folds[1]);
+        assert_eq!(
+            "#####\n#...#\n#...#\n#...#\n#####\n",
+            sheet.render().to_string()
+        );
+    }
+
+    #[test]
+    fn test_ocr() {
+        let image = Grid::parse_with(
+            "\
+#..#.####
+#..#..#..
+####..#..
+#..#..#..
+#..#..#..
+#..#.####
+",
+            "a pixel",
+            Some,
+        )
+        .unwrap();
+        assert_eq!(None, ocr(&image));
+
+        let image = Grid::parse_with(
+            "\
+#..#..###
+#..#...#.
+####...#.
+#..#...#.
+#..#...#.
+#..#..###
+",
+            "a pixel",
+            Some,
+        )
+        .unwrap();
+        assert_eq!(Some("HI".to_string()), ocr(&image));
+
+        let image = Grid::parse_with(
+            "\
+#....#..#
+#....#..#
+.#.#.####
+..#..#..#
+..#..#..#
+..#..#..#
+",
+            "a pixel",
+            Some,
+        )
+        .unwrap();
+        assert_eq!(Some("YH".to_string()), ocr(&image));
+    }
+}
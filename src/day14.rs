@@ -0,0 +1,201 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::iter::Iterator;
+
+use itertools::Itertools;
+
+use crate::parse::{self, Cursor, ParseError};
+use crate::Solution;
+
+pub struct RuleMap {
+    map: HashMap<(char, char), char>,
+}
+
+impl RuleMap {
+    fn new() -> RuleMap {
+        RuleMap {
+            map: HashMap::new(),
+        }
+    }
+
+    fn add_rule_str(&mut self, mut s: Cursor) -> Result<(), ParseError> {
+        let elem1 = read_element(&mut s)?;
+        let elem2 = read_element(&mut s)?;
+        s.literal(" -> ")?;
+        let ins = read_element(&mut s)?;
+        s.end()?;
+        self.map.insert((elem1, elem2), ins);
+        Ok(())
+    }
+
+    fn apply(&self, s: &str) -> String {
+        let mut result = String::new();
+        result.push(s.chars().next().unwrap());
+        for (e1, e2) in s.chars().tuple_windows() {
+            if let Some(ins) = self.map.get(&(e1, e2)) {
+                result.push(*ins);
+            }
+            result.push(e2);
+        }
+        result
+    }
+
+    fn apply_counts(&self, paircounts: &mut HashMap<(char, char), usize>) {
+        let mut newcounts = HashMap::<(char, char), usize>::new();
+        for ((c1, c2), n) in paircounts.iter() {
+            if let Some(ins) = self.map.get(&(*c1, *c2)) {
+                *newcounts.entry((*c1, *ins)).or_insert(0) += *n;
+                *newcounts.entry((*ins, *c2)).or_insert(0) += *n;
+            } else {
+                *newcounts.entry((*c1, *c2)).or_insert(0) += *n;
+            }
+        }
+        *paircounts = newcounts;
+    }
+}
+
+/// Counts the elements of the polymer after `iters` insertion steps. Only
+/// the number of each adjacent pair is tracked, so the polymer itself is
+/// never built.
+pub fn run(rules: &RuleMap, template: &str, iters: usize) -> HashMap<char, usize> {
+    let last = match template.chars().last() {
+        Some(c) => c,
+        None => return HashMap::new(),
+    };
+
+    let mut counts = HashMap::new();
+    for (e1, e2) in template.chars().tuple_windows() {
+        *counts.entry((e1, e2)).or_insert(0) += 1;
+    }
+    for _i in 0..iters {
+        rules.apply_counts(&mut counts);
+    }
+
+    // Every element except the last starts exactly one pair.
+    let mut elem_counts = HashMap::from([(last, 1)]);
+    for ((e1, _), n) in counts {
+        *elem_counts.entry(e1).or_insert(0) += n;
+    }
+    elem_counts
+}
+
+/// The difference between the most and least common element counts.
+fn spread(counts: &HashMap<char, usize>) -> usize {
+    match counts.values().minmax() {
+        itertools::MinMaxResult::MinMax(lce, mce) => mce - lce,
+        _ => 0,
+    }
+}
+
+fn read_element(s: &mut Cursor) -> Result<char, ParseError> {
+    match s.peek() {
+        Some(c) if c.is_ascii_uppercase() => s.next_char("an element"),
+        _ => Err(s.error("an element")),
+    }
+}
+
+pub struct Polymer {
+    template: String,
+    rules: RuleMap,
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Polymer;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Polymer, ParseError> {
+        let mut lines = parse::lines(input);
+        let mut first = lines
+            .next()
+            .ok_or_else(|| parse::eof_error(input, "a polymer template"))?;
+        let mut template = String::new();
+        template.push(read_element(&mut first)?);
+        while !first.is_empty() {
+            template.push(read_element(&mut first)?);
+        }
+        if let Some(blank) = lines.next() {
+            blank.end()?;
+        }
+        let mut rules = RuleMap::new();
+        for line in lines {
+            rules.add_rule_str(line)?;
+        }
+        Ok(Polymer { template, rules })
+    }
+
+    fn part1(polymer: &Polymer) -> usize {
+        let mut polymer_str = polymer.template.clone();
+        for _i in 0..10 {
+            polymer_str = polymer.rules.apply(&polymer_str);
+        }
+        spread(&polymer_str.chars().counts())
+    }
+
+    fn part2(polymer: &Polymer) -> usize {
+        spread(&run(&polymer.rules, &polymer.template, 40))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = "\
+NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C
+";
+
+    #[test]
+    fn test_run() {
+        let polymer = Day14::parse(INPUT).unwrap();
+        let counts = run(&polymer.rules, &polymer.template, 10);
+        assert_eq!(1749, counts[&'B']);
+        assert_eq!(161, counts[&'H']);
+        assert_eq!(
+            polymer.rules.apply("NNCB").chars().counts(),
+            run(&polymer.rules, "NNCB", 1)
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(1588, Day14::part1(&Day14::parse(INPUT).unwrap()));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(2188189693529, Day14::part2(&Day14::parse(INPUT).unwrap()));
+    }
+}
@@ -0,0 +1,176 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::iter::Iterator;
+use std::vec::Vec;
+
+use crate::grid::{Coord, Grid};
+use crate::parse::ParseError;
+use crate::Solution;
+
+/// The cave's risk levels. The map of risks is repeated `tiles` times in
+/// each direction, with every tile's risks one higher than the tile above
+/// or to the left of it, wrapping from 9 back to 1. Tiles are computed on
+/// access rather than stored.
+pub struct Cave<'a> {
+    risks: &'a Grid<u8>,
+    tiles: usize,
+}
+
+impl<'a> Cave<'a> {
+    pub fn new(risks: &'a Grid<u8>, tiles: usize) -> Cave<'a> {
+        Cave { risks, tiles }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.risks.rows() * self.tiles
+    }
+
+    pub fn cols(&self) -> usize {
+        self.risks.cols() * self.tiles
+    }
+
+    pub fn risk(&self, (r, c): Coord) -> u32 {
+        let (rows, cols) = (self.risks.rows(), self.risks.cols());
+        let base = self.risks[(r % rows, c % cols)] as u32;
+        let inc = (r / rows + c / cols) as u32;
+        (base - 1 + inc) % 9 + 1
+    }
+
+    fn neighbors(&self, (r, c): Coord) -> impl Iterator<Item = Coord> {
+        let (rows, cols) = (self.rows(), self.cols());
+        let adj = [
+            r.checked_sub(1).map(|r| (r, c)),
+            Some((r + 1, c)).filter(|_| r + 1 < rows),
+            c.checked_sub(1).map(|c| (r, c)),
+            Some((r, c + 1)).filter(|_| c + 1 < cols),
+        ];
+        adj.into_iter().flatten()
+    }
+
+    /// Finds the path from the top left to the bottom right corner with the
+    /// lowest total risk, using Dijkstra's algorithm. The risk of the
+    /// starting position is not counted.
+    pub fn lowest_risk_path(&self) -> Path {
+        let cols = self.cols();
+        let index = |(r, c): Coord| r * cols + c;
+        let end = (self.rows() - 1, cols - 1);
+
+        let mut dist = vec![u32::MAX; self.rows() * cols];
+        let mut prev: Vec<Option<Coord>> = vec![None; self.rows() * cols];
+        let mut queue = BinaryHeap::new();
+        dist[0] = 0;
+        queue.push(Reverse((0, (0, 0))));
+        while let Some(Reverse((d, pos))) = queue.pop() {
+            if pos == end {
+                break;
+            }
+            if d > dist[index(pos)] {
+                continue;
+            }
+            for n in self.neighbors(pos) {
+                let nd = d + self.risk(n);
+                if nd < dist[index(n)] {
+                    dist[index(n)] = nd;
+                    prev[index(n)] = Some(pos);
+                    queue.push(Reverse((nd, n)));
+                }
+            }
+        }
+
+        let mut cells = vec![end];
+        while let Some(p) = prev[index(*cells.last().unwrap())] {
+            cells.push(p);
+        }
+        cells.reverse();
+        Path {
+            risk: dist[index(end)],
+            cells,
+        }
+    }
+}
+
+/// A path through the cave, including both its start and end.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Path {
+    pub risk: u32,
+    pub cells: Vec<Coord>,
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Grid<u8>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+        Grid::parse_with(input, "a risk level from 1 to 9", |c| match c {
+            '1'..='9' => c.to_digit(10).map(|d| d as u8),
+            _ => None,
+        })
+    }
+
+    fn part1(risks: &Grid<u8>) -> u32 {
+        Cave::new(risks, 1).lowest_risk_path().risk
+    }
+
+    fn part2(risks: &Grid<u8>) -> u32 {
+        Cave::new(risks, 5).lowest_risk_path().risk
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = "\
+1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581
+";
+
+    #[test]
+    fn test_path() {
+        let risks = Day15::parse(INPUT).unwrap();
+        let cave = Cave::new(&risks, 5);
+        assert_eq!(2, cave.risk((0, 10)));
+        assert_eq!(2, cave.risk((0, 44)));
+
+        let path = cave.lowest_risk_path();
+        assert_eq!(Some(&(0, 0)), path.cells.first());
+        assert_eq!(Some(&(49, 49)), path.cells.last());
+        let total: u32 = path.cells[1..].iter().map(|p| cave.risk(*p)).sum();
+        assert_eq!(path.risk, total);
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(40, Day15::part1(&Day15::parse(INPUT).unwrap()));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(315, Day15::part2(&Day15::parse(INPUT).unwrap()));
+    }
+}
@@ -0,0 +1,270 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::iter::Iterator;
+use std::vec::Vec;
+
+use crate::bits::{self, BitReader};
+use crate::parse::{self, Cursor, ParseError};
+use crate::Solution;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Op {
+    Sum,
+    Product,
+    Minimum,
+    Maximum,
+    GreaterThan,
+    LessThan,
+    EqualTo,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Body {
+    Literal(u64),
+    Operator(Op, Vec<Packet>),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Packet {
+    pub version: u8,
+    pub body: Body,
+}
+
+impl Packet {
+    /// The sum of the version numbers of this packet and all packets
+    /// nested in it.
+    pub fn version_sum(&self) -> u64 {
+        let nested = match &self.body {
+            Body::Literal(_) => 0,
+            Body::Operator(_, operands) => operands.iter().map(Packet::version_sum).sum(),
+        };
+        self.version as u64 + nested
+    }
+
+    pub fn eval(&self) -> u64 {
+        let (op, operands) = match &self.body {
+            Body::Literal(v) => return *v,
+            Body::Operator(op, operands) => (op, operands),
+        };
+        let mut values = operands.iter().map(Packet::eval);
+        match op {
+            Op::Sum => values.sum(),
+            Op::Product => values.product(),
+            Op::Minimum => values.min().unwrap(),
+            Op::Maximum => values.max().unwrap(),
+            Op::GreaterThan => (values.next() > values.next()) as u64,
+            Op::LessThan => (values.next() < values.next()) as u64,
+            Op::EqualTo => (values.next() == values.next()) as u64,
+        }
+    }
+}
+
+/// Prints the expression a packet encodes, e.g. `((1 + 3) == min(2, 2))`.
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (op, operands) = match &self.body {
+            Body::Literal(v) => return write!(f, "{}", v),
+            Body::Operator(op, operands) => (op, operands),
+        };
+        let (open, sep) = match op {
+            Op::Sum => ("(", " + "),
+            Op::Product => ("(", " * "),
+            Op::Minimum => ("min(", ", "),
+            Op::Maximum => ("max(", ", "),
+            Op::GreaterThan => ("(", " > "),
+            Op::LessThan => ("(", " < "),
+            Op::EqualTo => ("(", " == "),
+        };
+        write!(f, "{}", open)?;
+        for (i, p) in operands.iter().enumerate() {
+            if i > 0 {
+                write!(f, "{}", sep)?;
+            }
+            write!(f, "{}", p)?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// Decodes packets from the bits of a line of hexadecimal input. Errors
+/// point at the hex digit holding the offending bit.
+struct Decoder<'a, 'b> {
+    line: &'b Cursor<'a>,
+    reader: BitReader<'b>,
+}
+
+impl<'a, 'b> Decoder<'a, 'b> {
+    fn column(&self) -> usize {
+        self.reader.pos() / 4 + 1
+    }
+
+    fn error(&self, expected: &str) -> ParseError {
+        self.line.error_at(self.column(), expected)
+    }
+
+    fn read(&mut self, n: usize, expected: &str) -> Result<u64, ParseError> {
+        self.reader.read(n).ok_or_else(|| self.error(expected))
+    }
+
+    fn packet(&mut self) -> Result<Packet, ParseError> {
+        let start = self.column();
+        let version = self.read(3, "a packet version")? as u8;
+        let op = match self.read(3, "a packet type")? {
+            0 => Op::Sum,
+            1 => Op::Product,
+            2 => Op::Minimum,
+            3 => Op::Maximum,
+            4 => {
+                return Ok(Packet {
+                    version,
+                    body: Body::Literal(self.literal()?),
+                })
+            }
+            5 => Op::GreaterThan,
+            6 => Op::LessThan,
+            _ => Op::EqualTo,
+        };
+
+        let mut operands = Vec::new();
+        if self.read(1, "a length type")? == 0 {
+            let len = self.read(15, "a sub-packet length")? as usize;
+            if len > self.reader.remaining() {
+                return Err(self.error(&format!("{} bits of sub-packets", len)));
+            }
+            let end = self.reader.pos() + len;
+            while self.reader.pos() < end {
+                operands.push(self.packet()?);
+            }
+            if self.reader.pos() > end {
+                return Err(self.error(&format!("sub-packets to end after {} bits", len)));
+            }
+        } else {
+            let count = self.read(11, "a sub-packet count")?;
+            for _ in 0..count {
+                operands.push(self.packet()?);
+            }
+        }
+
+        let arity_ok = match op {
+            Op::GreaterThan | Op::LessThan | Op::EqualTo => operands.len() == 2,
+            _ => !operands.is_empty(),
+        };
+        if !arity_ok {
+            return Err(self.line.error_at(start, "an operator with valid operands"));
+        }
+        Ok(Packet {
+            version,
+            body: Body::Operator(op, operands),
+        })
+    }
+
+    fn literal(&mut self) -> Result<u64, ParseError> {
+        let mut value: u64 = 0;
+        loop {
+            let more = self.read(1, "a literal group")? == 1;
+            if value.leading_zeros() < 4 {
+                return Err(self.error("a literal that fits in 64 bits"));
+            }
+            value = (value << 4) | self.read(4, "a literal group")?;
+            if !more {
+                return Ok(value);
+            }
+        }
+    }
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Packet;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Packet, ParseError> {
+        let mut line = parse::lines(input)
+            .next()
+            .ok_or_else(|| parse::eof_error(input, "a hexadecimal transmission"))?;
+        let bits = bits::parse_hex(&mut line)?;
+        let mut decoder = Decoder {
+            line: &line,
+            reader: BitReader::new(&bits),
+        };
+        decoder.packet()
+    }
+
+    fn part1(packet: &Packet) -> u64 {
+        packet.version_sum()
+    }
+
+    fn part2(packet: &Packet) -> u64 {
+        packet.eval()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        let packet = Day16::parse("D2FE28").unwrap();
+        assert_eq!(
+            Packet {
+                version: 6,
+                body: Body::Literal(2021)
+            },
+            packet
+        );
+
+        let packet = Day16::parse("38006F45291200").unwrap();
+        assert_eq!("(10 < 20)", packet.to_string());
+        let packet = Day16::parse("EE00D40C823060").unwrap();
+        assert_eq!("max(1, 2, 3)", packet.to_string());
+        let packet = Day16::parse("9C0141080250320F1802104A08").unwrap();
+        assert_eq!("((1 + 3) == (2 * 2))", packet.to_string());
+
+        let err = Day16::parse("D2FE").unwrap_err();
+        assert_eq!("a literal group", err.expected);
+    }
+
+    #[test]
+    fn test_part1() {
+        for (hex, sum) in [
+            ("8A004A801A8002F478", 16),
+            ("620080001611562C8802118E34", 12),
+            ("C0015000016115A2E0802F182340", 23),
+            ("A0016C880162017C3686B18A3D4780", 31),
+        ] {
+            assert_eq!(sum, Day16::part1(&Day16::parse(hex).unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_part2() {
+        for (hex, value) in [
+            ("C200B40A82", 3),
+            ("04005AC33890", 54),
+            ("880086C3E88112", 7),
+            ("CE00C43D881120", 9),
+            ("D8005AC2A8F0", 1),
+            ("F600BC2D8F", 0),
+            ("9C005AC2F8F0", 0),
+            ("9C0141080250320F1802104A08", 1),
+        ] {
+            assert_eq!(value, Day16::part2(&Day16::parse(hex).unwrap()));
+        }
+    }
+}
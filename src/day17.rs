@@ -0,0 +1,178 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::iter::Iterator;
+use std::ops::RangeInclusive;
+
+use crate::parse::{self, Cursor, ParseError};
+use crate::Solution;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Probe {
+    pub x: i32,
+    pub y: i32,
+    pub vx: i32,
+    pub vy: i32,
+}
+
+impl Probe {
+    pub fn launch(vx: i32, vy: i32) -> Probe {
+        Probe { x: 0, y: 0, vx, vy }
+    }
+
+    fn step(mut self) -> Probe {
+        self.x += self.vx;
+        self.y += self.vy;
+        self.vx -= self.vx.signum();
+        self.vy -= 1;
+        self
+    }
+
+    /// The positions of the probe after each step, without end.
+    pub fn trajectory(self) -> impl Iterator<Item = (i32, i32)> {
+        let mut probe = self;
+        std::iter::from_fn(move || {
+            probe = probe.step();
+            Some((probe.x, probe.y))
+        })
+    }
+}
+
+/// The target area, which lies to the right of and below the launch point.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Target {
+    pub x: RangeInclusive<i32>,
+    pub y: RangeInclusive<i32>,
+}
+
+impl Target {
+    pub fn contains(&self, (x, y): (i32, i32)) -> bool {
+        self.x.contains(&x) && self.y.contains(&y)
+    }
+
+    /// Launches a probe and returns the highest point it reaches if it
+    /// is ever inside the target after a step.
+    pub fn apex(&self, vx: i32, vy: i32) -> Option<i32> {
+        let mut apex = 0;
+        for pos in Probe::launch(vx, vy).trajectory() {
+            apex = apex.max(pos.1);
+            if self.contains(pos) {
+                return Some(apex);
+            }
+            if pos.0 > *self.x.end() || pos.1 < *self.y.start() {
+                return None;
+            }
+        }
+        unreachable!()
+    }
+
+    /// All initial velocities that reach the target, with their apexes.
+    ///
+    /// Only a bounded set of velocities needs to be tried. The probe must
+    /// reach the left edge before drag stops it, so `vx * (vx + 1) / 2` is at
+    /// least that edge, and it must not overshoot the right edge in the
+    /// first step. Going up, it falls back through `y = 0` with velocity
+    /// `-vy - 1`, so that step must not overshoot the bottom edge; going
+    /// down, the first step must not either.
+    pub fn hits(&self) -> impl Iterator<Item = ((i32, i32), i32)> + '_ {
+        let min_vx = (0..).find(|v| v * (v + 1) / 2 >= *self.x.start()).unwrap();
+        let max_vx = *self.x.end();
+        let min_vy = *self.y.start();
+        let max_vy = -self.y.start() - 1;
+        (min_vx..=max_vx)
+            .flat_map(move |vx| (min_vy..=max_vy).map(move |vy| (vx, vy)))
+            .filter_map(move |(vx, vy)| self.apex(vx, vy).map(|a| ((vx, vy), a)))
+    }
+}
+
+fn read_range(line: &mut Cursor, axis: &str) -> Result<RangeInclusive<i32>, ParseError> {
+    line.literal(axis)?;
+    line.literal("=")?;
+    let start = line.number()?;
+    line.literal("..")?;
+    let col = line.column();
+    let end = line.number()?;
+    if end < start {
+        return Err(line.error_at(col, format!("a number of at least {}", start)));
+    }
+    Ok(start..=end)
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Target;
+    type Answer1 = i32;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Target, ParseError> {
+        let mut line = parse::lines(input)
+            .next()
+            .ok_or_else(|| parse::eof_error(input, "a target area"))?;
+        line.literal("target area: ")?;
+        let x = read_range(&mut line, "x")?;
+        line.literal(", ")?;
+        let y = read_range(&mut line, "y")?;
+        line.end()?;
+        if *x.start() <= 0 || *y.end() >= 0 {
+            return Err(line.error_at(1, "a target area right of and below the launcher"));
+        }
+        Ok(Target { x, y })
+    }
+
+    fn part1(target: &Target) -> i32 {
+        target.hits().map(|(_, apex)| apex).max().unwrap_or(0)
+    }
+
+    fn part2(target: &Target) -> usize {
+        target.hits().count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = "target area: x=20..30, y=-10..-5\n";
+
+    #[test]
+    fn test_trajectory() {
+        let target = Day17::parse(INPUT).unwrap();
+        let points: Vec<_> = Probe::launch(7, 2).trajectory().take(7).collect();
+        assert_eq!(
+            vec![
+                (7, 2),
+                (13, 3),
+                (18, 3),
+                (22, 2),
+                (25, 0),
+                (27, -3),
+                (28, -7)
+            ],
+            points
+        );
+        assert!(target.contains(points[6]));
+        assert_eq!(None, target.apex(17, -4));
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(45, Day17::part1(&Day17::parse(INPUT).unwrap()));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(112, Day17::part2(&Day17::parse(INPUT).unwrap()));
+    }
+}
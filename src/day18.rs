@@ -0,0 +1,260 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::iter::Iterator;
+use std::ops::Add;
+use std::vec::Vec;
+
+use itertools::Itertools;
+
+use crate::parse::{self, Cursor, ParseError};
+use crate::Solution;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SnailNumber {
+    Regular(u32),
+    Pair(Box<SnailNumber>, Box<SnailNumber>),
+}
+
+use SnailNumber::*;
+
+/// A single step of reducing a snailfish number.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Action {
+    /// A pair of regular numbers nested inside four pairs exploded.
+    Explode(u32, u32),
+    /// A regular number of 10 or more split into a pair.
+    Split(u32),
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Action::Explode(a, b) => write!(f, "explode [{},{}]", a, b),
+            Action::Split(n) => write!(f, "split {}", n),
+        }
+    }
+}
+
+impl SnailNumber {
+    pub fn pair(left: SnailNumber, right: SnailNumber) -> SnailNumber {
+        Pair(Box::new(left), Box::new(right))
+    }
+
+    pub fn magnitude(&self) -> u32 {
+        match self {
+            Regular(n) => *n,
+            Pair(l, r) => 3 * l.magnitude() + 2 * r.magnitude(),
+        }
+    }
+
+    fn add_leftmost(&mut self, v: u32) {
+        match self {
+            Regular(n) => *n += v,
+            Pair(l, _) => l.add_leftmost(v),
+        }
+    }
+
+    fn add_rightmost(&mut self, v: u32) {
+        match self {
+            Regular(n) => *n += v,
+            Pair(_, r) => r.add_rightmost(v),
+        }
+    }
+
+    /// Explodes the leftmost pair nested at `depth` 4 or deeper. Returns the
+    /// exploded pair, along with the parts of it that still need to be
+    /// added to the regular numbers on either side.
+    fn explode(&mut self, depth: usize) -> Option<(Action, Option<u32>, Option<u32>)> {
+        let (l, r) = match self {
+            Regular(_) => return None,
+            Pair(l, r) => (l, r),
+        };
+        if depth >= 4 {
+            if let (Regular(a), Regular(b)) = (&**l, &**r) {
+                let (a, b) = (*a, *b);
+                *self = Regular(0);
+                return Some((Action::Explode(a, b), Some(a), Some(b)));
+            }
+        }
+        if let Some((action, left, right)) = l.explode(depth + 1) {
+            if let Some(v) = right {
+                r.add_leftmost(v);
+            }
+            return Some((action, left, None));
+        }
+        if let Some((action, left, right)) = r.explode(depth + 1) {
+            if let Some(v) = left {
+                l.add_rightmost(v);
+            }
+            return Some((action, None, right));
+        }
+        None
+    }
+
+    /// Splits the leftmost regular number of 10 or more.
+    fn split(&mut self) -> Option<Action> {
+        match self {
+            Regular(n) if *n >= 10 => {
+                let n = *n;
+                *self = SnailNumber::pair(Regular(n / 2), Regular(n.div_ceil(2)));
+                Some(Action::Split(n))
+            }
+            Regular(_) => None,
+            Pair(l, r) => l.split().or_else(|| r.split()),
+        }
+    }
+
+    /// Reduces the number, calling `trace` after each action with the
+    /// action taken and the resulting number.
+    pub fn reduce_with(&mut self, mut trace: impl FnMut(Action, &SnailNumber)) {
+        loop {
+            let action = match self.explode(0) {
+                Some((action, _, _)) => action,
+                None => match self.split() {
+                    Some(action) => action,
+                    None => return,
+                },
+            };
+            trace(action, self);
+        }
+    }
+
+    /// Adds two numbers like `+`, also returning every reduction action and
+    /// the number it produced.
+    pub fn add_traced(self, rhs: SnailNumber) -> (SnailNumber, Vec<(Action, SnailNumber)>) {
+        let mut sum = SnailNumber::pair(self, rhs);
+        let mut trace = Vec::new();
+        sum.reduce_with(|action, n| trace.push((action, n.clone())));
+        (sum, trace)
+    }
+
+    pub fn parse(line: &mut Cursor) -> Result<SnailNumber, ParseError> {
+        if line.peek() == Some('[') {
+            line.literal("[")?;
+            let left = SnailNumber::parse(line)?;
+            line.literal(",")?;
+            let right = SnailNumber::parse(line)?;
+            line.literal("]")?;
+            Ok(SnailNumber::pair(left, right))
+        } else if line.peek().is_some_and(|c| c.is_ascii_digit()) {
+            Ok(Regular(line.number()?))
+        } else {
+            Err(line.error("'[' or a number"))
+        }
+    }
+}
+
+impl Add for SnailNumber {
+    type Output = SnailNumber;
+
+    fn add(self, rhs: SnailNumber) -> SnailNumber {
+        let mut sum = SnailNumber::pair(self, rhs);
+        sum.reduce_with(|_, _| ());
+        sum
+    }
+}
+
+/// Prints the number in the same syntax it is parsed from.
+impl fmt::Display for SnailNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Regular(n) => write!(f, "{}", n),
+            Pair(l, r) => write!(f, "[{},{}]", l, r),
+        }
+    }
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Vec<SnailNumber>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<SnailNumber>, ParseError> {
+        parse::lines(input)
+            .map(|mut line| {
+                let n = SnailNumber::parse(&mut line)?;
+                line.end()?;
+                Ok(n)
+            })
+            .collect()
+    }
+
+    fn part1(nums: &Vec<SnailNumber>) -> u32 {
+        nums.iter()
+            .cloned()
+            .reduce(|a, b| a + b)
+            .map_or(0, |sum| sum.magnitude())
+    }
+
+    fn part2(nums: &Vec<SnailNumber>) -> u32 {
+        nums.iter()
+            .permutations(2)
+            .map(|p| (p[0].clone() + p[1].clone()).magnitude())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = "\
+[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
+[[[5,[2,8]],4],[5,[[9,9],0]]]
+[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]
+[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]
+[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]
+[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]
+[[[[5,4],[7,7]],8],[[8,3],8]]
+[[9,3],[[9,9],[6,[4,9]]]]
+[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
+[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]
+";
+
+    fn num(s: &str) -> SnailNumber {
+        SnailNumber::parse(&mut Cursor::new(1, s)).unwrap()
+    }
+
+    #[test]
+    fn test_add_traced() {
+        let (sum, trace) = num("[[[[4,3],4],4],[7,[[8,4],9]]]").add_traced(num("[1,1]"));
+        assert_eq!("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]", sum.to_string());
+        let trace: Vec<_> = trace.iter().map(|(a, n)| format!("{}: {}", a, n)).collect();
+        assert_eq!(
+            vec![
+                "explode [4,3]: [[[[0,7],4],[7,[[8,4],9]]],[1,1]]",
+                "explode [8,4]: [[[[0,7],4],[15,[0,13]]],[1,1]]",
+                "split 15: [[[[0,7],4],[[7,8],[0,13]]],[1,1]]",
+                "split 13: [[[[0,7],4],[[7,8],[0,[6,7]]]],[1,1]]",
+                "explode [6,7]: [[[[0,7],4],[[7,8],[6,0]]],[8,1]]",
+            ],
+            trace
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(4140, Day18::part1(&Day18::parse(INPUT).unwrap()));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(3993, Day18::part2(&Day18::parse(INPUT).unwrap()));
+    }
+}
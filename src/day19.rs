@@ -0,0 +1,382 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::iter::Iterator;
+use std::vec::Vec;
+
+use itertools::Itertools;
+
+use crate::parse::{self, ParseError};
+use crate::vec3::{Rotation, Vec3};
+use crate::Solution;
+
+/// The number of beacons two scanners must both detect to be aligned.
+const OVERLAP: usize = 12;
+
+/// The beacons one scanner detected, relative to its own position and
+/// orientation.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Scanner {
+    pub beacons: Vec<Vec3>,
+}
+
+impl Scanner {
+    /// The sorted squared distances between all pairs of beacons. They do
+    /// not depend on where the scanner is or how it is turned, so two
+    /// scanners that share 12 beacons share at least 66 of them.
+    fn fingerprint(&self) -> Vec<i32> {
+        let mut dists: Vec<_> = self
+            .beacons
+            .iter()
+            .tuple_combinations()
+            .map(|(a, b)| (*a - *b).length_squared())
+            .collect();
+        dists.sort_unstable();
+        dists
+    }
+}
+
+/// Counts the values two sorted lists have in common, with multiplicity.
+fn shared(a: &[i32], b: &[i32]) -> usize {
+    let (mut i, mut j, mut count) = (0, 0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            Ordering::Less => i += 1,
+            Ordering::Greater => j += 1,
+            Ordering::Equal => {
+                count += 1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    count
+}
+
+/// Where a scanner is and how it is turned relative to scanner 0. A beacon
+/// the scanner sees at `p` is at `rotation.apply(p) + position`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Placement {
+    pub position: Vec3,
+    pub rotation: Rotation,
+}
+
+impl Placement {
+    pub fn locate(&self, p: Vec3) -> Vec3 {
+        self.rotation.apply(p) + self.position
+    }
+}
+
+/// Finds a placement of `scanner` under which at least 12 of its beacons
+/// coincide with `known` ones.
+fn align(known: &[Vec3], scanner: &Scanner) -> Option<Placement> {
+    for rotation in Rotation::all() {
+        let mut offsets = HashMap::new();
+        for b in scanner.beacons.iter() {
+            let b = rotation.apply(*b);
+            for k in known.iter() {
+                let count = offsets.entry(*k - b).or_insert(0);
+                *count += 1;
+                if *count >= OVERLAP {
+                    return Some(Placement {
+                        position: *k - b,
+                        rotation,
+                    });
+                }
+            }
+        }
+    }
+    None
+}
+
+/// All scanners placed relative to scanner 0, along with every beacon in
+/// scanner 0's coordinates.
+#[derive(Clone, Debug)]
+pub struct Map {
+    pub scanners: Vec<Placement>,
+    pub beacons: HashSet<Vec3>,
+}
+
+/// Places each scanner by aligning it with one that is already placed,
+/// starting from scanner 0. Only pairs whose fingerprints overlap enough
+/// are tried. On failure, returns the index of a scanner that could not be
+/// placed.
+pub fn assemble(scanners: &[Scanner]) -> Result<Map, usize> {
+    let prints: Vec<_> = scanners.iter().map(Scanner::fingerprint).collect();
+    let min_shared = OVERLAP * (OVERLAP - 1) / 2;
+
+    let mut placed = vec![None; scanners.len()];
+    let mut located = vec![Vec::new(); scanners.len()];
+    placed[0] = Some(Placement {
+        position: Vec3::ZERO,
+        rotation: Rotation::IDENTITY,
+    });
+    located[0] = scanners[0].beacons.clone();
+    let mut queue = vec![0];
+    while let Some(i) = queue.pop() {
+        for j in 0..scanners.len() {
+            if placed[j].is_some() || shared(&prints[i], &prints[j]) < min_shared {
+                continue;
+            }
+            if let Some(p) = align(&located[i], &scanners[j]) {
+                located[j] = scanners[j].beacons.iter().map(|b| p.locate(*b)).collect();
+                placed[j] = Some(p);
+                queue.push(j);
+            }
+        }
+    }
+
+    let scanners = placed
+        .into_iter()
+        .enumerate()
+        .map(|(i, p)| p.ok_or(i))
+        .collect::<Result<_, _>>()?;
+    Ok(Map {
+        scanners,
+        beacons: located.into_iter().flatten().collect(),
+    })
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = Map;
+    type Answer1 = usize;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Map, ParseError> {
+        let mut lines = parse::lines(input);
+        let mut headers = Vec::new();
+        let mut scanners = Vec::new();
+        while let Some(mut header) = lines.next() {
+            header.literal("--- scanner ")?;
+            let col = header.column();
+            if header.number::<usize>()? != scanners.len() {
+                return Err(header.error_at(col, format!("scanner {}", scanners.len())));
+            }
+            header.literal(" ---")?;
+            header.end()?;
+
+            let mut beacons = Vec::new();
+            for mut line in lines.by_ref() {
+                if line.is_empty() {
+                    break;
+                }
+                beacons.push(Vec3::parse(&mut line)?);
+                line.end()?;
+            }
+            headers.push(header);
+            scanners.push(Scanner { beacons });
+        }
+        if scanners.is_empty() {
+            return Err(parse::eof_error(input, "a scanner report"));
+        }
+        assemble(&scanners).map_err(|i| {
+            headers[i].error_at(
+                1,
+                format!("a scanner sharing {} beacons with another", OVERLAP),
+            )
+        })
+    }
+
+    fn part1(map: &Map) -> usize {
+        map.beacons.len()
+    }
+
+    fn part2(map: &Map) -> i32 {
+        map.scanners
+            .iter()
+            .tuple_combinations()
+            .map(|(a, b)| (a.position - b.position).manhattan())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = "\
+--- scanner 0 ---
+404,-588,-901
+528,-643,409
+-838,591,734
+390,-675,-793
+-537,-823,-458
+-485,-357,347
+-345,-311,381
+-661,-816,-575
+-876,649,763
+-618,-824,-621
+553,345,-567
+474,580,667
+-447,-329,318
+-584,868,-557
+544,-627,-890
+564,392,-477
+455,729,728
+-892,524,684
+-689,845,-530
+423,-701,434
+7,-33,-71
+630,319,-379
+443,580,662
+-789,900,-551
+459,-707,401
+
+--- scanner 1 ---
+686,422,578
+605,423,415
+515,917,-361
+-336,658,858
+95,138,22
+-476,619,847
+-340,-569,-846
+567,-361,727
+-460,603,-452
+669,-402,600
+729,430,532
+-500,-761,534
+-322,571,750
+-466,-666,-811
+-429,-592,574
+-355,545,-477
+703,-491,-529
+-328,-685,520
+413,935,-424
+-391,539,-444
+586,-435,557
+-364,-763,-893
+807,-499,-711
+755,-354,-619
+553,889,-390
+
+--- scanner 2 ---
+649,640,665
+682,-795,504
+-784,533,-524
+-644,584,-595
+-588,-843,648
+-30,6,44
+-674,560,763
+500,723,-460
+609,671,-379
+-555,-800,653
+-675,-892,-343
+697,-426,-610
+578,704,681
+493,664,-388
+-671,-858,530
+-667,343,800
+571,-461,-707
+-138,-166,112
+-889,563,-600
+646,-828,498
+640,759,510
+-630,509,768
+-681,-892,-333
+673,-379,-804
+-742,-814,-386
+577,-820,562
+
+--- scanner 3 ---
+-589,542,597
+605,-692,669
+-500,565,-823
+-660,373,557
+-458,-679,-417
+-488,449,543
+-626,468,-788
+338,-750,-386
+528,-832,-391
+562,-778,733
+-938,-730,414
+543,643,-506
+-524,371,-870
+407,773,750
+-104,29,83
+378,-903,-323
+-778,-728,485
+426,699,580
+-438,-605,-362
+-469,-447,-387
+509,732,623
+647,635,-688
+-868,-804,481
+614,-800,639
+595,780,-596
+
+--- scanner 4 ---
+727,592,562
+-293,-554,779
+441,611,-461
+-714,465,-776
+-743,427,-804
+-660,-479,-426
+832,-632,460
+927,-485,-438
+408,393,-506
+466,436,-512
+110,16,151
+-258,-428,682
+-393,719,612
+-211,-452,876
+808,-476,-593
+-575,615,604
+-485,667,467
+-680,325,-822
+-627,-443,-432
+872,-547,-609
+833,512,582
+807,604,487
+839,-516,451
+891,-625,532
+-652,-548,-490
+30,-46,-14
+";
+
+    #[test]
+    fn test_assemble() {
+        let map = Day19::parse(INPUT).unwrap();
+        let positions: Vec<_> = map.scanners.iter().map(|p| p.position).collect();
+        assert_eq!(
+            vec![
+                Vec3::ZERO,
+                Vec3::new(68, -1246, -43),
+                Vec3::new(1105, -1205, 1229),
+                Vec3::new(-92, -2380, -20),
+                Vec3::new(-20, -1133, 1061),
+            ],
+            positions
+        );
+        assert_eq!("-x,y,-z", map.scanners[1].rotation.to_string());
+        assert_eq!(
+            Vec3::new(-618, -824, -621),
+            map.scanners[1].locate(Vec3::new(686, 422, 578))
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(79, Day19::part1(&Day19::parse(INPUT).unwrap()));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(3621, Day19::part2(&Day19::parse(INPUT).unwrap()));
+    }
+}
@@ -0,0 +1,142 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::parse::{self, Cursor, ParseError};
+use crate::Solution;
+
+#[derive(Clone, Copy, Debug)]
+pub enum Command {
+    Forward(i32),
+    Down(i32),
+    Up(i32),
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Pos {
+    depth: i32,
+    horizontal: i32,
+}
+
+fn parse_command(mut cmd: Cursor) -> Result<Command, ParseError> {
+    let dir_col = cmd.column();
+    let dir = cmd.word("a direction")?;
+    cmd.literal(" ")?;
+    let off_col = cmd.column();
+    let off = cmd.number()?;
+    cmd.end()?;
+    if off <= 0 {
+        return Err(cmd.error_at(off_col, "a positive number"));
+    }
+    match dir {
+        "forward" => Ok(Command::Forward(off)),
+        "down" => Ok(Command::Down(off)),
+        "up" => Ok(Command::Up(off)),
+        _ => Err(cmd.error_at(dir_col, "'forward', 'down' or 'up'")),
+    }
+}
+
+fn apply_command(mut pos: Pos, cmd: Command) -> Pos {
+    match cmd {
+        Command::Forward(off) => pos.horizontal += off,
+        Command::Down(off) => pos.depth += off,
+        Command::Up(off) => pos.depth -= off,
+    }
+    pos
+}
+
+#[derive(Clone, Copy, Debug)]
+struct State {
+    depth: i32,
+    horizontal: i32,
+    aim: i32,
+}
+
+fn apply_command_2(mut state: State, cmd: Command) -> State {
+    match cmd {
+        Command::Forward(off) => {
+            state.horizontal += off;
+            state.depth += off * state.aim;
+        }
+        Command::Down(off) => state.aim += off,
+        Command::Up(off) => state.aim -= off,
+    }
+    state
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Command>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Vec<Command>, ParseError> {
+        parse::lines(input).map(parse_command).collect()
+    }
+
+    fn part1(cmds: &Vec<Command>) -> i32 {
+        let pos = cmds.iter().copied().fold(
+            Pos {
+                depth: 0,
+                horizontal: 0,
+            },
+            apply_command,
+        );
+        pos.depth * pos.horizontal
+    }
+
+    fn part2(cmds: &Vec<Command>) -> i32 {
+        let pos = cmds.iter().copied().fold(
+            State {
+                depth: 0,
+                horizontal: 0,
+                aim: 0,
+            },
+            apply_command_2,
+        );
+        pos.depth * pos.horizontal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = "\
+forward 5
+down 5
+forward 8
+up 3
+down 8
+forward 2
+";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(150, Day2::part1(&Day2::parse(INPUT).unwrap()));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(900, Day2::part2(&Day2::parse(INPUT).unwrap()));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day2::parse("forward 5\nsideways 3\n").unwrap_err();
+        assert_eq!((2, 1), (err.line, err.column));
+        let err = Day2::parse("up -3\n").unwrap_err();
+        assert_eq!((1, 4), (err.line, err.column));
+    }
+}
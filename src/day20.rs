@@ -0,0 +1,220 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::iter::Iterator;
+use std::vec::Vec;
+
+use crate::bits::to_int;
+use crate::grid::Grid;
+use crate::parse::{self, Cursor, ParseError};
+use crate::Solution;
+
+/// An infinite image: a finite grid of pixels, surrounded on all sides by
+/// pixels that are all lit or all dark.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Image {
+    pub pixels: Grid<bool>,
+    pub background: bool,
+}
+
+impl Image {
+    fn pixel(&self, r: isize, c: isize) -> bool {
+        if r < 0 || c < 0 {
+            return self.background;
+        }
+        *self
+            .pixels
+            .get((r as usize, c as usize))
+            .unwrap_or(&self.background)
+    }
+
+    /// Applies the enhancement algorithm once. The grid grows by one pixel
+    /// on each side, since those are the only background pixels whose
+    /// windows overlap the grid. The rest of the background stays uniform:
+    /// a window of all dark pixels maps to entry 0 and one of all lit
+    /// pixels to entry 511.
+    pub fn enhance(&self, algorithm: &[bool]) -> Image {
+        let pixels = Grid::from_fn(self.pixels.rows() + 2, self.pixels.cols() + 2, |(r, c)| {
+            let (r, c) = (r as isize - 1, c as isize - 1);
+            let mut window = [false; 9];
+            for (i, bit) in window.iter_mut().enumerate() {
+                let (dr, dc) = (i as isize / 3 - 1, i as isize % 3 - 1);
+                *bit = self.pixel(r + dr, c + dc);
+            }
+            algorithm[to_int(&window) as usize]
+        });
+        let background = algorithm[if self.background { 511 } else { 0 }];
+        Image { pixels, background }
+    }
+
+    /// The successive images produced by repeated enhancement, starting
+    /// with the first enhanced one.
+    pub fn enhancements<'a>(&self, algorithm: &'a [bool]) -> impl Iterator<Item = Image> + 'a {
+        let mut image = self.clone();
+        std::iter::from_fn(move || {
+            image = image.enhance(algorithm);
+            Some(image.clone())
+        })
+    }
+
+    /// The number of lit pixels in the grid. If the background is lit, the
+    /// infinitely many lit pixels outside the grid are not counted.
+    pub fn lit(&self) -> usize {
+        self.pixels.iter().filter(|(_, p)| **p).count()
+    }
+}
+
+/// Prints the grid with `#` for lit and `.` for dark pixels.
+impl fmt::Display for Image {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.pixels.map(|p| if *p { '#' } else { '.' }))
+    }
+}
+
+fn pixel(c: char) -> Option<bool> {
+    match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    }
+}
+
+fn read_algorithm(mut line: Cursor) -> Result<Vec<bool>, ParseError> {
+    let mut algorithm = Vec::new();
+    while algorithm.len() < 512 {
+        let col = line.column();
+        let c = line.next_char("512 pixels")?;
+        algorithm.push(pixel(c).ok_or_else(|| line.error_at(col, "'#' or '.'"))?);
+    }
+    line.end()?;
+    Ok(algorithm)
+}
+
+#[derive(Clone, Debug)]
+pub struct Trench {
+    pub algorithm: Vec<bool>,
+    pub image: Image,
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = Trench;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Trench, ParseError> {
+        let mut lines = parse::lines(input);
+        let algorithm = read_algorithm(
+            lines
+                .next()
+                .ok_or_else(|| parse::eof_error(input, "an enhancement algorithm"))?,
+        )?;
+        lines
+            .next()
+            .ok_or_else(|| parse::eof_error(input, "an empty line"))?
+            .end()?;
+
+        // The grid is parsed on its own, so its line numbers are shifted
+        // past the two lines above.
+        let rest = input.splitn(3, '\n').nth(2).unwrap_or("");
+        let pixels = Grid::parse_with(rest, "'#' or '.'", pixel).map_err(|mut e| {
+            e.line += 2;
+            e
+        })?;
+        Ok(Trench {
+            algorithm,
+            image: Image {
+                pixels,
+                background: false,
+            },
+        })
+    }
+
+    fn part1(trench: &Trench) -> usize {
+        let mut images = trench.image.enhancements(&trench.algorithm);
+        images.nth(1).unwrap().lit()
+    }
+
+    fn part2(trench: &Trench) -> usize {
+        let mut images = trench.image.enhancements(&trench.algorithm);
+        images.nth(49).unwrap().lit()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = "\
+..#.#..#####.#.#.#.###.##.....###.##.#..###.####..#####..#....#..#..##..###..######.###...####..#..#####..##..#.#####...##.#.#..#.##..#.#......#.###.######.###.####...#.##.##..#..#..#####.....#.#....###..#.##......#.....#..#..#..##..#...##.######.####.####.#.#...#.......#..#.#.#...####.##.#......#..#...##.#.##..#...##.#.##..###.#......#.#.......#.#.#.####.###.##...#.....####.#..#..#.##.#....##..#.####....##...##..#...#......#.#.......#.......##..####..#...#.#.#...##..#.#..###..#####........#..####......#..#
+
+#..#.
+#....
+##..#
+..#..
+..###
+";
+
+    #[test]
+    fn test_enhance() {
+        let trench = Day20::parse(INPUT).unwrap();
+        assert_eq!(
+            "#..#.\n#....\n##..#\n..#..\n..###\n",
+            trench.image.to_string()
+        );
+        let image = trench.image.enhance(&trench.algorithm);
+        assert_eq!(
+            "\
+.##.##.
+#..#.#.
+##.#..#
+####..#
+.#..##.
+..##..#
+...#.#.
+",
+            image.to_string()
+        );
+
+        // With entry 0 lit and entry 511 dark, the background flips on
+        // every enhancement.
+        let mut flipping = vec![true; 512];
+        flipping[511] = false;
+        let backgrounds: Vec<_> = trench
+            .image
+            .enhancements(&flipping)
+            .take(3)
+            .map(|i| i.background)
+            .collect();
+        assert_eq!(vec![true, false, true], backgrounds);
+
+        let err = Day20::parse("#.#\n\n#\n").unwrap_err();
+        assert_eq!((1, 4), (err.line, err.column));
+        let mut bad_grid = INPUT.to_owned();
+        bad_grid.push_str("x\n");
+        assert_eq!(8, Day20::parse(&bad_grid).unwrap_err().line);
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(35, Day20::part1(&Day20::parse(INPUT).unwrap()));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(3351, Day20::part2(&Day20::parse(INPUT).unwrap()));
+    }
+}
@@ -0,0 +1,171 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::iter::Iterator;
+use std::ops::{AddAssign, Mul};
+
+use num::Zero;
+
+use crate::parse::{self, ParseError};
+use crate::Solution;
+
+fn advance(pos: u8, steps: u32) -> u8 {
+    ((pos as u32 + steps - 1) % 10 + 1) as u8
+}
+
+/// The end of a game with the deterministic die.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Outcome {
+    pub scores: [u32; 2],
+    pub rolls: u32,
+}
+
+/// Plays until someone reaches `target`, with a die that rolls 1 through
+/// 100 in order and then starts over.
+pub fn play_deterministic(start: [u8; 2], target: u32) -> Outcome {
+    let mut pos = start;
+    let mut scores = [0; 2];
+    let mut rolls = 0;
+    let mut die = (1..=100).cycle();
+    for player in (0..2).cycle() {
+        let steps: u32 = die.by_ref().take(3).sum();
+        rolls += 3;
+        pos[player] = advance(pos[player], steps);
+        scores[player] += pos[player] as u32;
+        if scores[player] >= target {
+            break;
+        }
+    }
+    Outcome { scores, rolls }
+}
+
+/// How many of the 27 ways to roll three Dirac dice give each total.
+const ROLLS: [(u32, u8); 7] = [(3, 1), (4, 3), (5, 6), (6, 7), (7, 6), (8, 3), (9, 1)];
+
+/// The state of a Dirac game, from the point of view of the player about to
+/// move.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct State {
+    pos: [u8; 2],
+    scores: [u32; 2],
+}
+
+/// Counts the universes in which each player wins from `state`, with the
+/// player to move first.
+fn wins<T>(memo: &mut HashMap<State, [T; 2]>, target: u32, state: State) -> [T; 2]
+where
+    T: Clone + Zero + AddAssign + Mul<Output = T> + From<u8>,
+{
+    if let Some(w) = memo.get(&state) {
+        return w.clone();
+    }
+    let mut total = [T::zero(), T::zero()];
+    for (steps, ways) in ROLLS {
+        let pos = advance(state.pos[0], steps);
+        let score = state.scores[0] + pos as u32;
+        if score >= target {
+            total[0] += T::from(ways);
+            continue;
+        }
+        let next = State {
+            pos: [state.pos[1], pos],
+            scores: [state.scores[1], score],
+        };
+        let [other, me] = wins(memo, target, next);
+        total[0] += me * T::from(ways);
+        total[1] += other * T::from(ways);
+    }
+    memo.insert(state, total.clone());
+    total
+}
+
+/// Counts the universes in which each player wins a game with the Dirac
+/// die, played to `target`. The count type can be a primitive integer or
+/// something like `BigUint` when that is not big enough.
+pub fn count_wins<T>(start: [u8; 2], target: u32) -> [T; 2]
+where
+    T: Clone + Zero + AddAssign + Mul<Output = T> + From<u8>,
+{
+    let state = State {
+        pos: start,
+        scores: [0, 0],
+    };
+    wins(&mut HashMap::new(), target, state)
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = [u8; 2];
+    type Answer1 = u32;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<[u8; 2], ParseError> {
+        let mut lines = parse::lines(input);
+        let mut start = [0; 2];
+        for (i, pos) in start.iter_mut().enumerate() {
+            let mut line = lines
+                .next()
+                .ok_or_else(|| parse::eof_error(input, format!("player {}", i + 1)))?;
+            line.literal(&format!("Player {} starting position: ", i + 1))?;
+            let col = line.column();
+            *pos = match line.number()? {
+                p @ 1..=10 => p,
+                _ => return Err(line.error_at(col, "a position from 1 to 10")),
+            };
+            line.end()?;
+        }
+        Ok(start)
+    }
+
+    fn part1(start: &[u8; 2]) -> u32 {
+        let outcome = play_deterministic(*start, 1000);
+        outcome.scores.iter().min().unwrap() * outcome.rolls
+    }
+
+    fn part2(start: &[u8; 2]) -> u64 {
+        count_wins::<u64>(*start, 21).into_iter().max().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use num::BigUint;
+
+    static INPUT: &str = "\
+Player 1 starting position: 4
+Player 2 starting position: 8
+";
+
+    #[test]
+    fn test_count_wins() {
+        let wins = count_wins::<u64>([4, 8], 21);
+        assert_eq!([444356092776315, 341960390180808], wins);
+        let big = count_wins::<BigUint>([4, 8], 21);
+        assert_eq!(wins.map(BigUint::from), big);
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(739785, Day21::part1(&Day21::parse(INPUT).unwrap()));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(444356092776315, Day21::part2(&Day21::parse(INPUT).unwrap()));
+    }
+}
@@ -0,0 +1,150 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::iter::{IntoIterator, Iterator};
+use std::vec::Vec;
+
+use crate::cuboid::Cuboid;
+use crate::parse::{self, Cursor, ParseError};
+use crate::Solution;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Step {
+    pub on: bool,
+    pub cuboid: Cuboid<i64>,
+}
+
+/// Counts the cubes that are on after running `steps`, starting with all
+/// cubes off.
+///
+/// Rather than tracking cubes, this keeps a signed count for each of a set
+/// of cuboids, so that the lit cubes are the sum of the cuboids' volumes
+/// times their counts. Each step cancels out its overlap with every
+/// cuboid counted so far, and then adds itself if it turns cubes on.
+pub fn reboot<'a>(steps: impl IntoIterator<Item = &'a Step>) -> i64 {
+    let mut counts: HashMap<Cuboid<i64>, i64> = HashMap::new();
+    for step in steps {
+        let overlaps: Vec<_> = counts
+            .iter()
+            .filter_map(|(c, n)| c.intersection(&step.cuboid).map(|i| (i, *n)))
+            .collect();
+        for (i, n) in overlaps {
+            *counts.entry(i).or_insert(0) -= n;
+        }
+        if step.on {
+            *counts.entry(step.cuboid).or_insert(0) += 1;
+        }
+        counts.retain(|_, n| *n != 0);
+    }
+    counts.iter().map(|(c, n)| c.volume() * n).sum()
+}
+
+fn read_range(line: &mut Cursor, axis: &str) -> Result<(i64, i64), ParseError> {
+    line.literal(axis)?;
+    line.literal("=")?;
+    let start = line.number()?;
+    line.literal("..")?;
+    let col = line.column();
+    let end = line.number()?;
+    if end < start {
+        return Err(line.error_at(col, format!("a number of at least {}", start)));
+    }
+    Ok((start, end))
+}
+
+fn read_step(mut line: Cursor) -> Result<Step, ParseError> {
+    let on = match line.word("'on' or 'off'")? {
+        "on" => true,
+        "off" => false,
+        _ => return Err(line.error_at(1, "'on' or 'off'")),
+    };
+    line.literal(" ")?;
+    let x = read_range(&mut line, "x")?;
+    line.literal(",")?;
+    let y = read_range(&mut line, "y")?;
+    line.literal(",")?;
+    let z = read_range(&mut line, "z")?;
+    line.end()?;
+    let cuboid = Cuboid::new([x.0, y.0, z.0], [x.1, y.1, z.1]).unwrap();
+    Ok(Step { on, cuboid })
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = Vec<Step>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<Step>, ParseError> {
+        parse::lines(input).map(read_step).collect()
+    }
+
+    /// Counts only the cubes in the initialization region.
+    fn part1(steps: &Vec<Step>) -> i64 {
+        let region = Cuboid::new([-50; 3], [50; 3]).unwrap();
+        let clipped: Vec<_> = steps
+            .iter()
+            .filter_map(|s| {
+                s.cuboid
+                    .intersection(&region)
+                    .map(|cuboid| Step { on: s.on, cuboid })
+            })
+            .collect();
+        reboot(&clipped)
+    }
+
+    fn part2(steps: &Vec<Step>) -> i64 {
+        reboot(steps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = "\
+on x=10..12,y=10..12,z=10..12
+on x=11..13,y=11..13,z=11..13
+off x=9..11,y=9..11,z=9..11
+on x=10..10,y=10..10,z=10..10
+on x=-54112..-39298,y=-85059..-49293,z=-27449..7877
+off x=40..60,y=-50..-40,z=0..0
+";
+
+    #[test]
+    fn test_reboot() {
+        let steps = Day22::parse(INPUT).unwrap();
+        assert_eq!(27, reboot(&steps[..1]));
+        assert_eq!(27 + 19, reboot(&steps[..2]));
+        assert_eq!(27 + 19 - 8, reboot(&steps[..3]));
+
+        let err = Day22::parse("on x=1..0,y=0..0,z=0..0\n").unwrap_err();
+        assert_eq!((1, 9), (err.line, err.column));
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(39, Day22::part1(&Day22::parse(INPUT).unwrap()));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(
+            39 + 14815 * 35767 * 35327,
+            Day22::part2(&Day22::parse(INPUT).unwrap())
+        );
+    }
+}
@@ -0,0 +1,517 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::fmt;
+use std::iter::Iterator;
+use std::vec::Vec;
+
+use crate::parse::{self, Cursor, ParseError};
+use crate::Solution;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Amphipod {
+    Amber,
+    Bronze,
+    Copper,
+    Desert,
+}
+
+use Amphipod::*;
+
+impl Amphipod {
+    const ALL: [Amphipod; 4] = [Amber, Bronze, Copper, Desert];
+
+    fn from_letter(c: char) -> Option<Amphipod> {
+        Some(match c {
+            'A' => Amber,
+            'B' => Bronze,
+            'C' => Copper,
+            'D' => Desert,
+            _ => return None,
+        })
+    }
+
+    pub fn letter(self) -> char {
+        ['A', 'B', 'C', 'D'][self as usize]
+    }
+
+    /// The energy it takes to move one step.
+    pub fn energy(self) -> u32 {
+        10u32.pow(self as u32)
+    }
+
+    /// The index of the room the amphipod belongs in.
+    fn home(self) -> usize {
+        self as usize
+    }
+}
+
+const HALL_LEN: usize = 11;
+const MAX_DEPTH: usize = 4;
+
+/// The hallway position just outside a room.
+fn door(room: usize) -> usize {
+    2 + 2 * room
+}
+
+/// A spot an amphipod can be in. Hallway positions count from the left,
+/// and room slots from the top.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Place {
+    Hallway(usize),
+    Room(usize, usize),
+}
+
+impl fmt::Display for Place {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Place::Hallway(x) => write!(f, "hallway {}", x),
+            Place::Room(r, slot) => write!(f, "room {} slot {}", Amphipod::ALL[*r].letter(), slot),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Move {
+    pub amphipod: Amphipod,
+    pub from: Place,
+    pub to: Place,
+    pub energy: u32,
+}
+
+/// Prints a move as in `B: room C slot 0 -> hallway 3 (40)`.
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} -> {} ({})",
+            self.amphipod.letter(),
+            self.from,
+            self.to,
+            self.energy
+        )
+    }
+}
+
+/// The cheapest way to organize a burrow.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Plan {
+    pub energy: u32,
+    pub moves: Vec<Move>,
+}
+
+/// The positions of all amphipods. Rooms have room for four amphipods, of
+/// which only the top `depth` slots are used, so states of either layout
+/// take the same 28 bytes.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Burrow {
+    hall: [Option<Amphipod>; HALL_LEN],
+    rooms: [[Option<Amphipod>; MAX_DEPTH]; 4],
+    depth: u8,
+}
+
+impl Burrow {
+    pub fn depth(&self) -> usize {
+        self.depth as usize
+    }
+
+    pub fn get(&self, place: Place) -> Option<Amphipod> {
+        match place {
+            Place::Hallway(x) => self.hall[x],
+            Place::Room(r, slot) => self.rooms[r][slot],
+        }
+    }
+
+    fn set(&mut self, place: Place, a: Option<Amphipod>) {
+        match place {
+            Place::Hallway(x) => self.hall[x] = a,
+            Place::Room(r, slot) => self.rooms[r][slot] = a,
+        }
+    }
+
+    /// Returns the burrow after moving the amphipod at `from` to `to`.
+    /// Whether the move is legal is not checked.
+    pub fn apply(&self, from: Place, to: Place) -> Burrow {
+        let mut next = *self;
+        next.set(to, self.get(from));
+        next.set(from, None);
+        next
+    }
+
+    /// Inserts the two folded-up rows of a 2-deep burrow's rooms. Panics if
+    /// the burrow is not 2 deep.
+    pub fn unfold(&self) -> Burrow {
+        const FOLDED: [[Amphipod; 2]; 4] = [
+            [Desert, Desert],
+            [Copper, Bronze],
+            [Bronze, Amber],
+            [Amber, Copper],
+        ];
+        assert_eq!(2, self.depth);
+        let mut next = *self;
+        next.depth = 4;
+        for (room, mid) in next.rooms.iter_mut().zip(FOLDED) {
+            *room = [room[0], Some(mid[0]), Some(mid[1]), room[1]];
+        }
+        next
+    }
+
+    pub fn is_organized(&self) -> bool {
+        (0..4).all(|r| {
+            self.rooms[r][..self.depth()]
+                .iter()
+                .all(|a| a.map(Amphipod::home) == Some(r))
+        })
+    }
+
+    /// Whether the hallway is empty between `from` and `to`, not counting
+    /// `from` itself.
+    fn hall_clear(&self, from: usize, to: usize) -> bool {
+        let path = if from < to {
+            from + 1..to + 1
+        } else {
+            to..from
+        };
+        self.hall[path].iter().all(Option::is_none)
+    }
+
+    /// The slot an amphipod entering room `r` would go to, if it only holds
+    /// amphipods that belong there and is not full.
+    fn vacancy(&self, r: usize) -> Option<usize> {
+        let room = &self.rooms[r][..self.depth()];
+        if room.iter().flatten().any(|a| a.home() != r) {
+            return None;
+        }
+        room.iter().rposition(Option::is_none)
+    }
+
+    /// The topmost amphipod in room `r`, if any amphipods there still have
+    /// to leave.
+    fn leaving(&self, r: usize) -> Option<(usize, Amphipod)> {
+        let room = &self.rooms[r][..self.depth()];
+        let top = room.iter().position(Option::is_some)?;
+        if room[top..].iter().flatten().all(|a| a.home() == r) {
+            return None;
+        }
+        Some((top, room[top].unwrap()))
+    }
+
+    fn step(&self, from: Place, to: Place, steps: usize) -> (Move, Burrow) {
+        let amphipod = self.get(from).unwrap();
+        let mv = Move {
+            amphipod,
+            from,
+            to,
+            energy: steps as u32 * amphipod.energy(),
+        };
+        (mv, self.apply(from, to))
+    }
+
+    /// All legal moves. If an amphipod can go straight into its room, that
+    /// is the only move returned, since doing it right away never costs
+    /// more.
+    fn moves(&self) -> Vec<(Move, Burrow)> {
+        for x in 0..HALL_LEN {
+            let a = match self.hall[x] {
+                Some(a) => a,
+                None => continue,
+            };
+            let home = a.home();
+            if let Some(slot) = self.vacancy(home) {
+                if self.hall_clear(x, door(home)) {
+                    let steps = x.abs_diff(door(home)) + slot + 1;
+                    return vec![self.step(Place::Hallway(x), Place::Room(home, slot), steps)];
+                }
+            }
+        }
+
+        let mut moves = Vec::new();
+        for r in 0..4 {
+            let (top, a) = match self.leaving(r) {
+                Some(l) => l,
+                None => continue,
+            };
+            let from = Place::Room(r, top);
+            let home = a.home();
+            if let Some(slot) = self.vacancy(home) {
+                if self.hall_clear(door(r), door(home)) {
+                    let steps = top + 1 + door(r).abs_diff(door(home)) + slot + 1;
+                    return vec![self.step(from, Place::Room(home, slot), steps)];
+                }
+            }
+            for x in (0..HALL_LEN).filter(|x| !(0..4).any(|r| door(r) == *x)) {
+                if self.hall_clear(door(r), x) {
+                    let steps = top + 1 + door(r).abs_diff(x);
+                    moves.push(self.step(from, Place::Hallway(x), steps));
+                }
+            }
+        }
+        moves
+    }
+
+    /// A lower bound on the energy still needed: every amphipod that is
+    /// not settled walks to its room's door and one step in, ignoring
+    /// others in the way, and the amphipods entering a room fill it from
+    /// one slot deep down to the bottom.
+    fn heuristic(&self) -> u32 {
+        let mut cost = 0;
+        let mut entering = [0usize; 4];
+        let mut add = |a: Amphipod, steps: usize| {
+            cost += steps as u32 * a.energy();
+            entering[a.home()] += 1;
+        };
+        for (x, a) in self.hall.iter().enumerate() {
+            if let Some(a) = a {
+                add(*a, x.abs_diff(door(a.home())) + 1);
+            }
+        }
+        for r in 0..4 {
+            let room = &self.rooms[r][..self.depth()];
+            for (slot, a) in room.iter().enumerate() {
+                let a = match a {
+                    Some(a) => *a,
+                    None => continue,
+                };
+                let blocking = room[slot..].iter().flatten().any(|b| b.home() != r);
+                if a.home() != r {
+                    add(a, slot + 1 + door(r).abs_diff(door(a.home())) + 1);
+                } else if blocking {
+                    // Out of the room, a step aside and back, and in.
+                    add(a, slot + 1 + 2 + 1);
+                }
+            }
+        }
+        for a in Amphipod::ALL {
+            let n = entering[a.home()];
+            cost += (n * n.saturating_sub(1) / 2) as u32 * a.energy();
+        }
+        cost
+    }
+
+    /// Finds the cheapest sequence of moves that gets every amphipod into
+    /// its room, using A* search. Returns `None` if there is no way.
+    pub fn organize(&self) -> Option<Plan> {
+        let mut best = HashMap::new();
+        let mut prev: HashMap<Burrow, (Burrow, Move)> = HashMap::new();
+        let mut queue = BinaryHeap::new();
+        best.insert(*self, 0);
+        queue.push(Reverse((self.heuristic(), 0, *self)));
+        while let Some(Reverse((_, energy, burrow))) = queue.pop() {
+            if burrow.is_organized() {
+                let mut moves = Vec::new();
+                let mut cur = burrow;
+                while let Some((p, mv)) = prev.get(&cur) {
+                    moves.push(*mv);
+                    cur = *p;
+                }
+                moves.reverse();
+                return Some(Plan { energy, moves });
+            }
+            if energy > best[&burrow] {
+                continue;
+            }
+            for (mv, next) in burrow.moves() {
+                let e = energy + mv.energy;
+                if best.get(&next).is_none_or(|b| e < *b) {
+                    best.insert(next, e);
+                    prev.insert(next, (burrow, mv));
+                    queue.push(Reverse((e + next.heuristic(), e, next)));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Prints the burrow as in the puzzle input.
+impl fmt::Display for Burrow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cell = |a: Option<Amphipod>| a.map_or('.', Amphipod::letter);
+        writeln!(f, "#############")?;
+        write!(f, "#")?;
+        for a in self.hall {
+            write!(f, "{}", cell(a))?;
+        }
+        writeln!(f, "#")?;
+        for slot in 0..self.depth() {
+            write!(f, "{}", if slot == 0 { "###" } else { "  #" })?;
+            for room in self.rooms.iter() {
+                write!(f, "{}#", cell(room[slot]))?;
+            }
+            writeln!(f, "{}", if slot == 0 { "##" } else { "" })?;
+        }
+        writeln!(f, "  #########")
+    }
+}
+
+/// Reads a row of room slots, or returns `None` for the burrow's bottom
+/// wall.
+fn read_row(mut line: Cursor) -> Result<Option<[Amphipod; 4]>, ParseError> {
+    line.take_while(|c| c == ' ' || c == '#');
+    if line.is_empty() {
+        return Ok(None);
+    }
+    let mut row = [Amber; 4];
+    for a in row.iter_mut() {
+        let col = line.column();
+        let c = line.next_char("an amphipod")?;
+        *a = Amphipod::from_letter(c).ok_or_else(|| line.error_at(col, "an amphipod"))?;
+        line.literal("#")?;
+    }
+    line.take_while(|c| c == '#');
+    line.end()?;
+    Ok(Some(row))
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input = Burrow;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Burrow, ParseError> {
+        let mut lines = parse::lines(input);
+        let mut burrow = Burrow {
+            hall: [None; HALL_LEN],
+            rooms: [[None; MAX_DEPTH]; 4],
+            depth: 0,
+        };
+        for wall in ["#############", "#...........#"] {
+            let mut line = lines
+                .next()
+                .ok_or_else(|| parse::eof_error(input, format!("'{}'", wall)))?;
+            line.literal(wall)?;
+            line.end()?;
+        }
+        let first = lines.next();
+        let mut cur = first.clone();
+        let bottom = loop {
+            let line = cur.ok_or_else(|| parse::eof_error(input, "a row of rooms"))?;
+            let row = match read_row(line.clone())? {
+                Some(row) => row,
+                None => break line,
+            };
+            if burrow.depth() == MAX_DEPTH {
+                return Err(line.error_at(1, "the bottom of the burrow"));
+            }
+            let slot = burrow.depth();
+            for (room, a) in burrow.rooms.iter_mut().zip(row) {
+                room[slot] = Some(a);
+            }
+            burrow.depth += 1;
+            cur = lines.next();
+        };
+
+        // Only the folded-up and the unfolded layout exist.
+        if burrow.depth != 2 && burrow.depth != 4 {
+            return Err(bottom.error_at(1, "a row of rooms"));
+        }
+        let first = first.unwrap();
+        for a in Amphipod::ALL {
+            let count = burrow
+                .rooms
+                .iter()
+                .flatten()
+                .filter(|b| **b == Some(a))
+                .count();
+            if count != burrow.depth() {
+                return Err(first.error_at(
+                    1,
+                    format!("{} amphipods of type {}", burrow.depth, a.letter()),
+                ));
+            }
+        }
+        Ok(burrow)
+    }
+
+    fn part1(burrow: &Burrow) -> u32 {
+        burrow.organize().unwrap().energy
+    }
+
+    /// Unfolds a 2-deep burrow first; a 4-deep one is already unfolded.
+    fn part2(burrow: &Burrow) -> u32 {
+        let burrow = match burrow.depth() {
+            2 => burrow.unfold(),
+            _ => *burrow,
+        };
+        burrow.organize().unwrap().energy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = "\
+#############
+#...........#
+###B#C#B#D###
+  #A#D#C#A#
+  #########
+";
+
+    #[test]
+    fn test_organize() {
+        let burrow = Day23::parse(INPUT).unwrap();
+        assert_eq!(INPUT, burrow.to_string());
+
+        let plan = burrow.organize().unwrap();
+        let mut replayed = burrow;
+        for mv in plan.moves.iter() {
+            assert_eq!(Some(mv.amphipod), replayed.get(mv.from));
+            assert_eq!(None, replayed.get(mv.to));
+            replayed = replayed.apply(mv.from, mv.to);
+        }
+        assert!(replayed.is_organized());
+        assert_eq!(plan.energy, plan.moves.iter().map(|m| m.energy).sum());
+
+        let err = Day23::parse(&INPUT.replace("###B", "###A")).unwrap_err();
+        assert_eq!("2 amphipods of type A", err.expected);
+
+        let shallow = INPUT.replace("  #A#D#C#A#\n", "");
+        let err = Day23::parse(&shallow).unwrap_err();
+        assert_eq!((4, "a row of rooms"), (err.line, err.expected.as_str()));
+        let three = INPUT.replace("  #A#D#C#A#\n", "  #A#D#C#A#\n  #A#B#C#D#\n");
+        assert_eq!(6, Day23::parse(&three).unwrap_err().line);
+    }
+
+    #[test]
+    fn test_unfolded() {
+        let unfolded = "\
+#############
+#...........#
+###B#C#B#D###
+  #D#C#B#A#
+  #D#B#A#C#
+  #A#D#C#A#
+  #########
+";
+        let burrow = Day23::parse(unfolded).unwrap();
+        assert_eq!(Day23::parse(INPUT).unwrap().unfold(), burrow);
+        assert_eq!(44169, Day23::part2(&burrow));
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(12521, Day23::part1(&Day23::parse(INPUT).unwrap()));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(44169, Day23::part2(&Day23::parse(INPUT).unwrap()));
+    }
+}
@@ -0,0 +1,431 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::error::Error;
+use std::fmt;
+use std::iter::{IntoIterator, Iterator};
+use std::vec::Vec;
+
+use crate::parse::{self, Cursor, ParseError};
+use crate::Solution;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Reg {
+    W,
+    X,
+    Y,
+    Z,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Operand {
+    Reg(Reg),
+    Num(i64),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Instruction {
+    Inp(Reg),
+    Add(Reg, Operand),
+    Mul(Reg, Operand),
+    Div(Reg, Operand),
+    Mod(Reg, Operand),
+    Eql(Reg, Operand),
+}
+
+fn read_reg(line: &mut Cursor) -> Result<Reg, ParseError> {
+    let col = line.column();
+    match line.next_char("a register")? {
+        'w' => Ok(Reg::W),
+        'x' => Ok(Reg::X),
+        'y' => Ok(Reg::Y),
+        'z' => Ok(Reg::Z),
+        _ => Err(line.error_at(col, "a register")),
+    }
+}
+
+fn read_operand(line: &mut Cursor) -> Result<Operand, ParseError> {
+    match line.peek() {
+        Some(c) if c.is_ascii_alphabetic() => Ok(Operand::Reg(read_reg(line)?)),
+        _ => Ok(Operand::Num(line.number()?)),
+    }
+}
+
+fn read_instruction(mut line: Cursor) -> Result<Instruction, ParseError> {
+    let col = line.column();
+    let op = line.word("an instruction")?;
+    line.literal(" ")?;
+    let a = read_reg(&mut line)?;
+    let instr = if op == "inp" {
+        Instruction::Inp(a)
+    } else {
+        line.literal(" ")?;
+        let b = read_operand(&mut line)?;
+        match op {
+            "add" => Instruction::Add(a, b),
+            "mul" => Instruction::Mul(a, b),
+            "div" => Instruction::Div(a, b),
+            "mod" => Instruction::Mod(a, b),
+            "eql" => Instruction::Eql(a, b),
+            _ => return Err(line.error_at(col, "an instruction")),
+        }
+    };
+    line.end()?;
+    Ok(instr)
+}
+
+pub fn parse_program(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    parse::lines(input).map(read_instruction).collect()
+}
+
+/// Why a program could not run to the end. Each holds the index of the
+/// failing instruction.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AluError {
+    InputExhausted(usize),
+    DivideByZero(usize),
+    /// A `mod` with a negative dividend or a non-positive divisor.
+    InvalidModulo(usize),
+    /// A result that does not fit in 64 bits.
+    Overflow(usize),
+}
+
+impl fmt::Display for AluError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AluError::InputExhausted(i) => write!(f, "instruction {}: no input left", i),
+            AluError::DivideByZero(i) => write!(f, "instruction {}: division by zero", i),
+            AluError::InvalidModulo(i) => write!(f, "instruction {}: invalid modulo", i),
+            AluError::Overflow(i) => write!(f, "instruction {}: overflow", i),
+        }
+    }
+}
+
+impl Error for AluError {}
+
+/// The registers of the arithmetic logic unit.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Alu {
+    regs: [i64; 4],
+}
+
+impl Alu {
+    pub fn get(&self, reg: Reg) -> i64 {
+        self.regs[reg as usize]
+    }
+
+    fn value(&self, operand: Operand) -> i64 {
+        match operand {
+            Operand::Reg(r) => self.get(r),
+            Operand::Num(n) => n,
+        }
+    }
+
+    /// Runs `program` from all-zero registers, reading `inp` values from
+    /// `input`, and returns the final registers.
+    pub fn run(
+        program: &[Instruction],
+        input: impl IntoIterator<Item = i64>,
+    ) -> Result<Alu, AluError> {
+        let mut alu = Alu::default();
+        let mut input = input.into_iter();
+        for (i, instr) in program.iter().enumerate() {
+            let overflow = AluError::Overflow(i);
+            let (dest, result) = match *instr {
+                Instruction::Inp(a) => (a, input.next().ok_or(AluError::InputExhausted(i))?),
+                Instruction::Add(a, b) => {
+                    (a, alu.get(a).checked_add(alu.value(b)).ok_or(overflow)?)
+                }
+                Instruction::Mul(a, b) => {
+                    (a, alu.get(a).checked_mul(alu.value(b)).ok_or(overflow)?)
+                }
+                Instruction::Div(a, b) => match alu.value(b) {
+                    0 => return Err(AluError::DivideByZero(i)),
+                    b => (a, alu.get(a).checked_div(b).ok_or(overflow)?),
+                },
+                Instruction::Mod(a, b) => match (alu.get(a), alu.value(b)) {
+                    (x, y) if x < 0 || y <= 0 => return Err(AluError::InvalidModulo(i)),
+                    (x, y) => (a, x % y),
+                },
+                Instruction::Eql(a, b) => (a, (alu.get(a) == alu.value(b)) as i64),
+            };
+            alu.regs[dest as usize] = result;
+        }
+        Ok(alu)
+    }
+}
+
+const DIGITS: usize = 14;
+const BLOCK_LEN: usize = 18;
+
+/// The source of one of MONAD's blocks, which all differ only in three
+/// constants.
+///
+/// `z` is used as a stack of base-26 digits. The block reads a digit `w`
+/// and compares it to the top of the stack plus `check`. If `pop` is 26
+/// rather than 1, the top is popped first. If the comparison fails, `w +
+/// offset` is pushed.
+fn block_source(pop: i64, check: i64, offset: i64) -> String {
+    format!(
+        "\
+inp w
+mul x 0
+add x z
+mod x 26
+div z {}
+add x {}
+eql x w
+eql x 0
+mul y 0
+add y 25
+mul y x
+add y 1
+mul z y
+mul y 0
+add y w
+add y {}
+mul y x
+add z y
+",
+        pop, check, offset
+    )
+}
+
+/// MONAD's requirement that digit `j` is digit `i` plus `diff`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Constraint {
+    pub i: usize,
+    pub j: usize,
+    pub diff: i64,
+}
+
+/// A way in which a program does not look like MONAD.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AnalysisError {
+    /// The index of the first instruction that does not fit, which is the
+    /// length of the program if it is too short.
+    pub instruction: usize,
+    pub expected: String,
+}
+
+/// Works out which digits MONAD compares against each other.
+///
+/// Blocks that do not pop have a `check` above 9, so they always push.
+/// For `z` to end up at 0, every popping block must then not push, so its
+/// digit has to equal the pushed one plus that block's `offset` plus the
+/// popping block's `check`.
+pub fn analyze(program: &[Instruction]) -> Result<Vec<Constraint>, AnalysisError> {
+    let error = |instruction: usize, expected: &str| AnalysisError {
+        instruction,
+        expected: expected.to_owned(),
+    };
+    let mut stack = Vec::new();
+    let mut constraints = Vec::new();
+    for j in 0..DIGITS {
+        let start = j * BLOCK_LEN;
+        let block = program
+            .get(start..start + BLOCK_LEN)
+            .ok_or_else(|| error(program.len(), "14 blocks of 18 instructions"))?;
+        let num = |k: usize| match block[k] {
+            Instruction::Div(_, Operand::Num(n)) | Instruction::Add(_, Operand::Num(n)) => n,
+            _ => 0,
+        };
+        let (pop, check, offset) = (num(4), num(5), num(15));
+        let expected = parse_program(&block_source(pop, check, offset)).unwrap();
+        if let Some(k) = (0..BLOCK_LEN).find(|k| block[*k] != expected[*k]) {
+            return Err(error(start + k, "an instruction of a MONAD block"));
+        }
+
+        match pop {
+            1 if check > 9 => stack.push((j, offset)),
+            1 => return Err(error(start + 5, "a check above 9")),
+            26 => {
+                let (i, pushed) = stack
+                    .pop()
+                    .ok_or_else(|| error(start + 4, "a block that pushes a digit"))?;
+                let diff = pushed + check;
+                if diff.abs() > 8 {
+                    return Err(error(start + 5, "a check that some digit can pass"));
+                }
+                constraints.push(Constraint { i, j, diff });
+            }
+            _ => return Err(error(start + 4, "'div z 1' or 'div z 26'")),
+        }
+    }
+    if program.len() > DIGITS * BLOCK_LEN {
+        return Err(error(DIGITS * BLOCK_LEN, "the end of the program"));
+    }
+    if let Some((i, _)) = stack.first() {
+        return Err(error(
+            i * BLOCK_LEN + 4,
+            "as many popping blocks as pushing ones",
+        ));
+    }
+    Ok(constraints)
+}
+
+/// The digits of the largest or smallest model number that satisfies all
+/// constraints.
+pub fn model_number(constraints: &[Constraint], largest: bool) -> Vec<i64> {
+    let mut digits = vec![0; DIGITS];
+    for c in constraints {
+        let d = if largest {
+            9.min(9 - c.diff)
+        } else {
+            1.max(1 - c.diff)
+        };
+        digits[c.i] = d;
+        digits[c.j] = d + c.diff;
+    }
+    digits
+}
+
+fn to_number(digits: &[i64]) -> u64 {
+    digits.iter().fold(0, |n, d| n * 10 + *d as u64)
+}
+
+#[derive(Clone, Debug)]
+pub struct Monad {
+    pub program: Vec<Instruction>,
+    pub constraints: Vec<Constraint>,
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input = Monad;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Monad, ParseError> {
+        let program = parse_program(input)?;
+        let constraints =
+            analyze(&program).map_err(|e| match parse::lines(input).nth(e.instruction) {
+                Some(line) => line.error_at(1, e.expected),
+                None => parse::eof_error(input, e.expected),
+            })?;
+        Ok(Monad {
+            program,
+            constraints,
+        })
+    }
+
+    fn part1(monad: &Monad) -> u64 {
+        to_number(&model_number(&monad.constraints, true))
+    }
+
+    fn part2(monad: &Monad) -> u64 {
+        to_number(&model_number(&monad.constraints, false))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monad_source() -> String {
+        [
+            (1, 11, 5),
+            (1, 12, 7),
+            (26, -3, 0),
+            (1, 13, 1),
+            (26, -8, 0),
+            (1, 10, 10),
+            (1, 14, 2),
+            (26, -2, 0),
+            (1, 15, 3),
+            (26, -9, 0),
+            (26, -10, 0),
+            (1, 11, 6),
+            (26, -1, 0),
+            (26, -4, 0),
+        ]
+        .iter()
+        .map(|(pop, check, offset)| block_source(*pop, *check, *offset))
+        .collect()
+    }
+
+    #[test]
+    fn test_run() {
+        let program = parse_program(
+            "\
+inp w
+add z w
+mod z 2
+div w 2
+add y w
+mod y 2
+div w 2
+add x w
+mod x 2
+div w 2
+mod w 2
+",
+        )
+        .unwrap();
+        let alu = Alu::run(&program, [13]).unwrap();
+        let regs = [Reg::W, Reg::X, Reg::Y, Reg::Z].map(|r| alu.get(r));
+        assert_eq!([1, 1, 0, 1], regs);
+        assert_eq!(Err(AluError::InputExhausted(0)), Alu::run(&program, []));
+
+        let program = parse_program("inp x\nmod x y\n").unwrap();
+        assert_eq!(Err(AluError::InvalidModulo(1)), Alu::run(&program, [1]));
+
+        let program = parse_program("inp x\nmul x x\n").unwrap();
+        assert_eq!(Err(AluError::Overflow(1)), Alu::run(&program, [i64::MAX]));
+        let program = parse_program("inp x\ninp y\ndiv x y\n").unwrap();
+        assert_eq!(
+            Err(AluError::Overflow(2)),
+            Alu::run(&program, [i64::MIN, -1])
+        );
+    }
+
+    #[test]
+    fn test_analyze() {
+        let monad = Day24::parse(&monad_source()).unwrap();
+        for largest in [true, false] {
+            let digits = model_number(&monad.constraints, largest);
+            let alu = Alu::run(&monad.program, digits.iter().copied()).unwrap();
+            assert_eq!(0, alu.get(Reg::Z));
+        }
+        let mut digits = model_number(&monad.constraints, true);
+        digits[13] -= 1;
+        let alu = Alu::run(&monad.program, digits).unwrap();
+        assert_ne!(0, alu.get(Reg::Z));
+
+        let err = Day24::parse(&monad_source().replacen("mul y x", "mul y z", 1)).unwrap_err();
+        assert_eq!(
+            (11, "an instruction of a MONAD block".to_owned()),
+            (err.line, err.expected)
+        );
+        let err = Day24::parse(&monad_source().replacen("div z 26", "div z 1", 1)).unwrap_err();
+        assert_eq!(42, err.line);
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(
+            85992999939499,
+            Day24::part1(&Day24::parse(&monad_source()).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(
+            11581111711162,
+            Day24::part2(&Day24::parse(&monad_source()).unwrap())
+        );
+    }
+}
@@ -0,0 +1,218 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::iter::Iterator;
+use std::vec::Vec;
+
+use crate::grid::{Coord, Grid};
+use crate::parse::ParseError;
+use crate::Solution;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Cell {
+    Empty,
+    East,
+    South,
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let c = match self {
+            Cell::Empty => '.',
+            Cell::East => '>',
+            Cell::South => 'v',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+/// Moves every sea cucumber of `herd` that has room to, all at once, and
+/// returns how many moved. Sea cucumbers leaving one edge of the floor
+/// come back in on the opposite edge.
+fn move_herd(floor: &mut Grid<Cell>, herd: Cell) -> usize {
+    let (rows, cols) = (floor.rows(), floor.cols());
+    let ahead = |(r, c): Coord| match herd {
+        Cell::East => (r, (c + 1) % cols),
+        _ => ((r + 1) % rows, c),
+    };
+    let movers: Vec<Coord> = floor
+        .iter()
+        .filter(|(pos, cell)| **cell == herd && floor[ahead(*pos)] == Cell::Empty)
+        .map(|(pos, _)| pos)
+        .collect();
+    for pos in movers.iter() {
+        floor[*pos] = Cell::Empty;
+        floor[ahead(*pos)] = herd;
+    }
+    movers.len()
+}
+
+/// Moves the east-facing herd and then the south-facing herd, and returns
+/// how many sea cucumbers moved.
+pub fn step(floor: &mut Grid<Cell>) -> usize {
+    move_herd(floor, Cell::East) + move_herd(floor, Cell::South)
+}
+
+/// The sea floor after a step.
+#[derive(Clone, Debug)]
+pub struct Step {
+    pub floor: Grid<Cell>,
+    pub moved: usize,
+}
+
+/// Iterator over the successive states of the sea floor, starting with
+/// the state after the first step.
+pub struct Steps {
+    floor: Grid<Cell>,
+}
+
+impl Iterator for Steps {
+    type Item = Step;
+
+    fn next(&mut self) -> Option<Step> {
+        let moved = step(&mut self.floor);
+        Some(Step {
+            floor: self.floor.clone(),
+            moved,
+        })
+    }
+}
+
+pub fn steps(floor: &Grid<Cell>) -> Steps {
+    Steps {
+        floor: floor.clone(),
+    }
+}
+
+/// When the sea cucumbers stop moving.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Stop {
+    /// On this step, counting from 1, no sea cucumber moves.
+    Step(usize),
+    /// The herds move in a cycle forever.
+    Never,
+}
+
+impl fmt::Display for Stop {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Stop::Step(n) => write!(f, "{}", n),
+            Stop::Never => write!(f, "never (the herds move in a cycle)"),
+        }
+    }
+}
+
+/// Finds the first step on which no sea cucumber moves. Cycles are found
+/// with Brent's algorithm, which only keeps one earlier state around.
+pub fn first_stop(floor: &Grid<Cell>) -> Stop {
+    let mut saved = floor.clone();
+    let mut limit = 1;
+    let mut since = 0;
+    for (i, step) in steps(floor).enumerate() {
+        if step.moved == 0 {
+            return Stop::Step(i + 1);
+        }
+        if step.floor == saved {
+            return Stop::Never;
+        }
+        since += 1;
+        if since == limit {
+            saved = step.floor;
+            limit *= 2;
+            since = 0;
+        }
+    }
+    unreachable!()
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input = Grid<Cell>;
+    type Answer1 = Stop;
+    type Answer2 = &'static str;
+
+    fn parse(input: &str) -> Result<Grid<Cell>, ParseError> {
+        Grid::parse_with(input, "'.', '>' or 'v'", |c| match c {
+            '.' => Some(Cell::Empty),
+            '>' => Some(Cell::East),
+            'v' => Some(Cell::South),
+            _ => None,
+        })
+    }
+
+    fn part1(floor: &Grid<Cell>) -> Stop {
+        first_stop(floor)
+    }
+
+    /// Day 25 has no second puzzle.
+    fn part2(_floor: &Grid<Cell>) -> &'static str {
+        "none"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = "\
+v...>>.vv>
+.vv>>.vv..
+>>.>v>...v
+>>v>>.>.v.
+v>v.vv.v..
+>.>>..v...
+.vv..>.>v.
+v.v..>>v.v
+....v..v.>
+";
+
+    #[test]
+    fn test_steps() {
+        let floor = Day25::parse("...>>>>>...\n").unwrap();
+        let states: Vec<_> = steps(&floor).take(2).collect();
+        assert_eq!("...>>>>.>..\n", states[0].floor.to_string());
+        assert_eq!(1, states[0].moved);
+        assert_eq!("...>>>.>.>.\n", states[1].floor.to_string());
+        assert_eq!(2, states[1].moved);
+
+        let mut floor = Day25::parse("..........\n.>v....v..\n.......>..\n..........\n").unwrap();
+        assert_eq!(3, step(&mut floor));
+        assert_eq!(
+            "..........\n.>........\n..v....v>.\n..........\n",
+            floor.to_string()
+        );
+
+        let mut floor = Day25::parse("v.\n>.\n").unwrap();
+        assert_eq!(2, step(&mut floor));
+        assert_eq!("..\nv>\n", floor.to_string());
+    }
+
+    #[test]
+    fn test_first_stop() {
+        assert_eq!(Stop::Never, first_stop(&Day25::parse(">.\n").unwrap()));
+        assert_eq!(
+            Stop::Never,
+            first_stop(&Day25::parse(">..\n.v.\n").unwrap())
+        );
+        assert_eq!(Stop::Step(1), first_stop(&Day25::parse(">>\n").unwrap()));
+        assert_eq!("58", Stop::Step(58).to_string());
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Stop::Step(58), Day25::part1(&Day25::parse(INPUT).unwrap()));
+    }
+}
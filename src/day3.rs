@@ -0,0 +1,143 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::bits::{self, to_int};
+use crate::parse::{self, ParseError};
+use crate::Solution;
+
+fn count_bit(nums: &[Vec<bool>], pos: usize) -> Option<usize> {
+    let mut count = 0;
+    for n in nums {
+        if pos >= n.len() {
+            return None;
+        }
+        if n[pos] {
+            count += 1;
+        }
+    }
+    Some(count)
+}
+
+fn gamma_epsilon(nums: &[Vec<bool>]) -> (u32, u32) {
+    let mut gamma = 0;
+    let mut epsilon = 0;
+    for pos in 0.. {
+        gamma <<= 1;
+        epsilon <<= 1;
+        if let Some(count) = count_bit(nums, pos) {
+            if count > nums.len() / 2 {
+                gamma += 1;
+            } else {
+                epsilon += 1;
+            }
+        } else {
+            break;
+        }
+    }
+    (gamma >> 1, epsilon >> 1)
+}
+
+/// Narrows down `nums`, which must not be empty, bit by bit. A bit that
+/// all remaining numbers share keeps them all, so numbers that occur more
+/// than once end up with one of them.
+fn rating(mut nums: Vec<Vec<bool>>, criteria: bool) -> u32 {
+    let mut pos = 0;
+    while let Some(count) = count_bit(&nums, pos) {
+        if nums.len() <= 1 {
+            break;
+        }
+        let keep = criteria != ((count * 2) >= nums.len());
+        if nums.iter().any(|n| n[pos] == keep) {
+            nums.retain(|n| n[pos] == keep);
+        }
+        pos += 1;
+    }
+    to_int(&nums[0]) as u32
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<Vec<bool>>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<Vec<bool>>, ParseError> {
+        let mut nums: Vec<Vec<bool>> = Vec::new();
+        for line in parse::lines(input) {
+            let num = bits::parse_binary(&mut line.clone())?;
+            if let Some(first) = nums.first() {
+                if num.len() != first.len() {
+                    return Err(line.error(format!("{} bits", first.len())));
+                }
+            }
+            nums.push(num);
+        }
+        if nums.is_empty() {
+            return Err(parse::eof_error(input, "a binary number"));
+        }
+        Ok(nums)
+    }
+
+    fn part1(nums: &Vec<Vec<bool>>) -> u32 {
+        let (gamma, epsilon) = gamma_epsilon(nums);
+        gamma * epsilon
+    }
+
+    fn part2(nums: &Vec<Vec<bool>>) -> u32 {
+        let o2 = rating(nums.clone(), true);
+        let co2 = rating(nums.clone(), false);
+        o2 * co2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = "\
+00100
+11110
+10110
+10111
+10101
+01111
+00111
+11100
+10000
+11001
+00010
+01010
+";
+
+    #[test]
+    fn test_edge_cases() {
+        let err = Day3::parse("").unwrap_err();
+        assert_eq!((1, "a binary number"), (err.line, err.expected.as_str()));
+
+        let nums = Day3::parse("101\n101\n").unwrap();
+        assert_eq!(10, Day3::part1(&nums));
+        assert_eq!(25, Day3::part2(&nums));
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(198, Day3::part1(&Day3::parse(INPUT).unwrap()));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(230, Day3::part2(&Day3::parse(INPUT).unwrap()));
+    }
+}
@@ -0,0 +1,224 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::iter::{IntoIterator, Iterator};
+use std::vec::Vec;
+
+use crate::parse::{self, Cursor, ParseError};
+use crate::Solution;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Board {
+    cells: [u8; 25],
+    indices: [Option<u8>; 100],
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+struct Victory {
+    round: usize,
+    score: usize,
+}
+
+impl Board {
+    /// Reads a board from its next 5 lines of 5 numbers below 100 each.
+    fn read<'a>(
+        input: &str,
+        lines: &mut impl Iterator<Item = Cursor<'a>>,
+    ) -> Result<Board, ParseError> {
+        let mut cells = [0; 25];
+        let mut indices = [None; 100];
+        for row in cells.chunks_mut(5) {
+            let mut line = lines
+                .next()
+                .ok_or_else(|| parse::eof_error(input, "a row of 5 numbers"))?;
+            for c in row.iter_mut() {
+                line.skip_whitespace();
+                *c = read_cell(&mut line)?;
+            }
+            line.skip_whitespace();
+            line.end()?;
+        }
+        for (i, c) in cells.iter().enumerate() {
+            indices[*c as usize] = Some(i as u8);
+        }
+
+        Ok(Board { cells, indices })
+    }
+
+    fn check(&self, draws: impl IntoIterator<Item = u8>) -> Option<Victory> {
+        let mut rows: [u8; 5] = [0; 5];
+        let mut cols: [u8; 5] = [0; 5];
+        for (s, d) in draws.into_iter().enumerate() {
+            let i = match self.indices[d as usize] {
+                Some(i) => i,
+                None => continue,
+            };
+            let r = i / 5;
+            let c = i % 5;
+            rows[r as usize] |= 1 << c;
+            cols[c as usize] |= 1 << r;
+
+            if rows[r as usize] == 0b11111 || cols[c as usize] == 0b11111 {
+                let mut unmarked_sum: usize = 0;
+                for (r, row) in rows.iter().enumerate() {
+                    for c in 0..5 {
+                        if 0 == row & (1 << c) {
+                            unmarked_sum += self.cells[r * 5 + c] as usize;
+                        }
+                    }
+                }
+                return Some(Victory {
+                    round: s,
+                    score: d as usize * unmarked_sum,
+                });
+            }
+        }
+
+        None
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Bingo {
+    draws: Vec<u8>,
+    boards: Vec<Board>,
+}
+
+impl Bingo {
+    /// How each board that eventually wins does so.
+    fn victories(&self) -> impl Iterator<Item = Victory> + '_ {
+        self.boards
+            .iter()
+            .filter_map(|b| b.check(self.draws.iter().copied()))
+    }
+}
+
+fn read_cell(line: &mut Cursor) -> Result<u8, ParseError> {
+    let col = line.column();
+    match line.number()? {
+        n if n < 100 => Ok(n),
+        _ => Err(line.error_at(col, "a number below 100")),
+    }
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Bingo;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Bingo, ParseError> {
+        let mut lines = parse::lines(input);
+        let mut first = lines
+            .next()
+            .ok_or_else(|| parse::eof_error(input, "a list of draws"))?;
+        let draws = first.separated(',', read_cell)?;
+        first.end()?;
+
+        let mut lines = lines.peekable();
+        let mut boards = Vec::new();
+        while let Some(blank) = lines.next() {
+            if !blank.is_empty() {
+                return Err(blank.error("a blank line before the next board"));
+            }
+            if boards.is_empty() || lines.peek().is_some() {
+                boards.push(Board::read(input, &mut lines)?);
+            }
+        }
+        if boards.is_empty() {
+            return Err(parse::eof_error(input, "a blank line and a board"));
+        }
+
+        let bingo = Bingo { draws, boards };
+        if bingo.victories().next().is_none() {
+            return Err(first.error_at(1, "draws with which some board wins"));
+        }
+        Ok(bingo)
+    }
+
+    fn part1(bingo: &Bingo) -> usize {
+        bingo.victories().min().map_or(0, |v| v.score)
+    }
+
+    fn part2(bingo: &Bingo) -> usize {
+        bingo.victories().max().map_or(0, |v| v.score)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = "\
+7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7
+";
+
+    #[test]
+    fn test_parse_errors() {
+        let err =
+            Day4::parse(&INPUT.replace("22 13 17 11  0\n", "22 13 17 11  0 8\n")).unwrap_err();
+        assert_eq!(
+            (3, 16, "end of line"),
+            (err.line, err.column, err.expected.as_str())
+        );
+
+        let err = Day4::parse(&INPUT.replace(" 8  2 23  4 24", " 8  2 23  4")).unwrap_err();
+        assert_eq!(
+            (4, 12, "a number"),
+            (err.line, err.column, err.expected.as_str())
+        );
+
+        let err = Day4::parse(&INPUT.replace("\n\n 3 15", "\n 3 15")).unwrap_err();
+        assert_eq!(
+            (8, "a blank line before the next board"),
+            (err.line, err.expected.as_str())
+        );
+
+        let err = Day4::parse("1,2\n\n 3  4  5  6  7\n 8  9 10 11 12\n13 14 15 16 17\n18 19 20 21 22\n23 24 25 26 27\n")
+            .unwrap_err();
+        assert_eq!(
+            (1, "draws with which some board wins"),
+            (err.line, err.expected.as_str())
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(4512, Day4::part1(&Day4::parse(INPUT).unwrap()));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(1924, Day4::part2(&Day4::parse(INPUT).unwrap()));
+    }
+}
@@ -14,39 +14,43 @@
 
 use std::cmp::Ordering;
 use std::collections::HashMap;
-use std::io;
-use std::io::prelude::*;
 use std::iter::{self, IntoIterator, Iterator};
 use std::vec::Vec;
 
+use crate::parse::{self, Cursor, ParseError};
+use crate::Solution;
+
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-struct Point {
+pub struct Point {
     x: i32,
     y: i32,
 }
 
 impl Point {
-    fn parse(desc: &str) -> Point {
-        let mut iter = desc.split(',');
-        let x = iter.next().unwrap().parse().unwrap();
-        let y = iter.next().unwrap().parse().unwrap();
-        Point { x, y }
+    fn parse(desc: &mut Cursor) -> Result<Point, ParseError> {
+        let x = desc.number()?;
+        desc.literal(",")?;
+        let y = desc.number()?;
+        Ok(Point { x, y })
     }
 }
 
 #[derive(Clone, Copy, Debug)]
-struct Line {
+pub struct Line {
     p1: Point,
     p2: Point,
 }
 
 impl Line {
-    fn parse(desc: &str) -> Line {
-        let mut iter = desc.split(' ');
-        let p1 = Point::parse(iter.next().unwrap());
-        assert_eq!(iter.next().unwrap(), "->");
-        let p2 = Point::parse(iter.next().unwrap());
-        Line { p1, p2 }
+    fn parse(mut desc: Cursor) -> Result<Line, ParseError> {
+        let p1 = Point::parse(&mut desc)?;
+        desc.literal(" -> ")?;
+        let p2 = Point::parse(&mut desc)?;
+        desc.end()?;
+        if (p1.x - p2.x).abs() != (p1.y - p2.y).abs() && p1.x != p2.x && p1.y != p2.y {
+            return Err(desc.error_at(1, "a horizontal, vertical or 45 degree line"));
+        }
+        Ok(Line { p1, p2 })
     }
 
     fn is_not_diagonal(&self) -> bool {
@@ -89,14 +93,22 @@ fn overlaps(lines: impl IntoIterator<Item = Line>) -> usize {
     overlaps.values().filter(|e| **e >= 2).count()
 }
 
-fn main() {
-    let lines: Vec<Line> = io::BufReader::new(io::stdin())
-        .lines()
-        .map(|l| Line::parse(&l.unwrap()))
-        .collect();
-    println!(
-        "{}",
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Vec<Line>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Line>, ParseError> {
+        parse::lines(input).map(Line::parse).collect()
+    }
+
+    fn part1(lines: &Vec<Line>) -> usize {
         overlaps(lines.iter().filter(|l| l.is_not_diagonal()).copied())
-    );
-    println!("{}", overlaps(lines.iter().copied()));
+    }
+
+    fn part2(lines: &Vec<Line>) -> usize {
+        overlaps(lines.iter().copied())
+    }
 }
@@ -13,13 +13,14 @@
 // limitations under the License.
 
 use std::default::Default;
-use std::io;
-use std::io::prelude::*;
 use std::iter::{IntoIterator, Iterator};
 use std::vec::Vec;
 
 use num::BigUint;
 
+use crate::parse::{self, ParseError};
+use crate::Solution;
+
 fn simulate(times: impl IntoIterator<Item = u8>, cycles: usize) -> BigUint {
     let mut counts: [BigUint; 9] = Default::default();
     for t in times.into_iter() {
@@ -44,13 +45,33 @@ fn simulate(times: impl IntoIterator<Item = u8>, cycles: usize) -> BigUint {
     counts.iter().sum()
 }
 
-fn main() {
-    let input = io::BufReader::new(io::stdin())
-        .lines()
-        .next()
-        .unwrap()
-        .unwrap();
-    let times: Vec<u8> = input.split(',').map(|s| s.parse().unwrap()).collect();
-    println!("{}", simulate(times.iter().copied(), 80));
-    println!("{}", simulate(times.iter().copied(), 256));
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Vec<u8>;
+    type Answer1 = BigUint;
+    type Answer2 = BigUint;
+
+    fn parse(input: &str) -> Result<Vec<u8>, ParseError> {
+        let mut line = parse::lines(input)
+            .next()
+            .ok_or_else(|| parse::eof_error(input, "a list of timers"))?;
+        let times = line.separated(',', |l| {
+            let col = l.column();
+            match l.number()? {
+                t if t < 9 => Ok(t),
+                _ => Err(l.error_at(col, "a timer below 9")),
+            }
+        })?;
+        line.end()?;
+        Ok(times)
+    }
+
+    fn part1(times: &Vec<u8>) -> BigUint {
+        simulate(times.iter().copied(), 80)
+    }
+
+    fn part2(times: &Vec<u8>) -> BigUint {
+        simulate(times.iter().copied(), 256)
+    }
 }
@@ -12,11 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::io;
-use std::io::prelude::*;
 use std::iter::Iterator;
 use std::vec::Vec;
 
+use crate::parse::{self, ParseError};
+use crate::Solution;
+
 const MAX_POSITION: usize = 2000;
 
 fn calc(positions: &[u16], inc: bool) -> u64 {
@@ -37,23 +38,36 @@ fn calc(positions: &[u16], inc: bool) -> u64 {
         }
     }
 
-    let (min_pos, min_cost) = costs
-        .iter()
-        .copied()
-        .enumerate()
-        .min_by_key(|(_p, c)| *c)
-        .unwrap();
-    println!("min_pos = {}", min_pos);
-    min_cost
+    costs.iter().copied().min().unwrap()
 }
 
-fn main() {
-    let input = io::BufReader::new(io::stdin())
-        .lines()
-        .next()
-        .unwrap()
-        .unwrap();
-    let positions: Vec<u16> = input.split(',').map(|s| s.parse().unwrap()).collect();
-    println!("{}", calc(&positions, false));
-    println!("{}", calc(&positions, true));
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<u16>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<u16>, ParseError> {
+        let mut line = parse::lines(input)
+            .next()
+            .ok_or_else(|| parse::eof_error(input, "a list of positions"))?;
+        let positions = line.separated(',', |l| {
+            let col = l.column();
+            match l.number()? {
+                p if (p as usize) < MAX_POSITION => Ok(p),
+                _ => Err(l.error_at(col, format!("a position below {}", MAX_POSITION))),
+            }
+        })?;
+        line.end()?;
+        Ok(positions)
+    }
+
+    fn part1(positions: &Vec<u16>) -> u64 {
+        calc(positions, false)
+    }
+
+    fn part2(positions: &Vec<u16>) -> u64 {
+        calc(positions, true)
+    }
 }
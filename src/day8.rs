@@ -0,0 +1,295 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::error::Error;
+use std::fmt;
+use std::iter::Iterator;
+use std::vec::Vec;
+
+use crate::parse::{self, Cursor, ParseError};
+use crate::Solution;
+
+/// A set of segments or wires `a` through `g`, with `a` as the lowest bit.
+pub type Segments = u8;
+
+/// The segments lit for each digit on a correctly wired display.
+const DIGITS: [Segments; 10] = [
+    0b1110111, 0b0100100, 0b1011101, 0b1101101, 0b0101110, 0b1101011, 0b1111011, 0b0100101,
+    0b1111111, 0b1101111,
+];
+
+fn bit(c: char) -> Segments {
+    1 << (c as u8 - b'a')
+}
+
+/// Formats a set of segments as its letters, e.g. "acf".
+pub fn letters(s: Segments) -> String {
+    ('a'..='g').filter(|c| s & bit(*c) != 0).collect()
+}
+
+fn digit(segments: Segments) -> Option<u8> {
+    DIGITS.iter().position(|d| *d == segments).map(|d| d as u8)
+}
+
+/// Why the wiring of a display could not be deduced.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum WiringError {
+    /// There is not exactly one pattern with the given number of wires,
+    /// which every display must have for 1 and 4.
+    NoUniquePattern(u32),
+    /// The wire is lit in a number of patterns that does not match any
+    /// segment, or the same segment was deduced for several wires.
+    AmbiguousWire(char),
+    /// The pattern does not show a digit under the deduced wiring, or
+    /// shows the same digit as another pattern.
+    Undecodable(Segments),
+}
+
+impl fmt::Display for WiringError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WiringError::NoUniquePattern(len) => {
+                write!(f, "no unique pattern with {} wires", len)
+            }
+            WiringError::AmbiguousWire(w) => write!(f, "wire '{}' matches no single segment", w),
+            WiringError::Undecodable(s) => write!(f, "pattern '{}' is not a digit", letters(*s)),
+        }
+    }
+}
+
+impl Error for WiringError {}
+
+/// The segment that each wire of a display is connected to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Wiring {
+    segments: [Segments; 7],
+}
+
+impl Wiring {
+    /// The segment that `wire` lights.
+    pub fn segment(&self, wire: char) -> char {
+        let s = self.segments[(wire as u8 - b'a') as usize];
+        (b'a' + s.trailing_zeros() as u8) as char
+    }
+
+    /// Translates a set of wires to the set of segments they light.
+    pub fn translate(&self, wires: Segments) -> Segments {
+        (0..7)
+            .filter(|w| wires & (1 << w) != 0)
+            .fold(0, |s, w| s | self.segments[w])
+    }
+
+    pub fn decode(&self, wires: Segments) -> Option<u8> {
+        digit(self.translate(wires))
+    }
+}
+
+impl fmt::Display for Wiring {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, w) in ('a'..='g').enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}->{}", w, self.segment(w))?;
+        }
+        Ok(())
+    }
+}
+
+/// One line of notes: the ten unique signal patterns and the four output
+/// digits of a display.
+#[derive(Clone, Debug)]
+pub struct Entry {
+    pub patterns: [Segments; 10],
+    pub outputs: [Segments; 4],
+}
+
+impl Entry {
+    fn unique_pattern(&self, len: u32) -> Result<Segments, WiringError> {
+        let mut found = self.patterns.iter().filter(|p| p.count_ones() == len);
+        match (found.next(), found.next()) {
+            (Some(p), None) => Ok(*p),
+            _ => Err(WiringError::NoUniquePattern(len)),
+        }
+    }
+
+    /// Deduces the wiring from how many patterns each wire appears in. On a
+    /// correct display, segments b, e and f appear in a unique number of
+    /// digits; of the rest, c is part of 1 but a is not, and d is part of 4
+    /// but g is not.
+    pub fn deduce(&self) -> Result<Wiring, WiringError> {
+        let one = self.unique_pattern(2)?;
+        let four = self.unique_pattern(4)?;
+
+        let mut wiring = Wiring { segments: [0; 7] };
+        for (i, w) in ('a'..='g').enumerate() {
+            let wire = bit(w);
+            let count = self.patterns.iter().filter(|p| *p & wire != 0).count();
+            let segment = match count {
+                4 => 'e',
+                6 => 'b',
+                9 => 'f',
+                8 if one & wire != 0 => 'c',
+                8 => 'a',
+                7 if four & wire != 0 => 'd',
+                7 => 'g',
+                _ => return Err(WiringError::AmbiguousWire(w)),
+            };
+            if wiring.segments.contains(&bit(segment)) {
+                return Err(WiringError::AmbiguousWire(w));
+            }
+            wiring.segments[i] = bit(segment);
+        }
+
+        let mut seen = [false; 10];
+        for p in self.patterns.iter() {
+            match wiring.decode(*p) {
+                Some(d) if !seen[d as usize] => seen[d as usize] = true,
+                _ => return Err(WiringError::Undecodable(*p)),
+            }
+        }
+        Ok(wiring)
+    }
+
+    /// Reads the four output digits as a number.
+    pub fn decode(&self) -> Result<u32, WiringError> {
+        let wiring = self.deduce()?;
+        self.outputs.iter().try_fold(0, |n, o| {
+            let d = wiring.decode(*o).ok_or(WiringError::Undecodable(*o))?;
+            Ok(n * 10 + d as u32)
+        })
+    }
+}
+
+fn read_pattern(line: &mut Cursor) -> Result<Segments, ParseError> {
+    let mut pattern = 0;
+    while let Some(c @ 'a'..='g') = line.peek() {
+        if pattern & bit(c) != 0 {
+            return Err(line.error("a wire not already in the pattern"));
+        }
+        pattern |= bit(c);
+        line.next_char("a wire")?;
+    }
+    if pattern == 0 {
+        return Err(line.error("a pattern of wires 'a' to 'g'"));
+    }
+    Ok(pattern)
+}
+
+fn read_entry(mut line: Cursor) -> Result<Entry, ParseError> {
+    let mut patterns = [0; 10];
+    for (i, p) in patterns.iter_mut().enumerate() {
+        if i > 0 {
+            line.literal(" ")?;
+        }
+        *p = read_pattern(&mut line)?;
+    }
+    line.literal(" | ")?;
+    let mut outputs = [0; 4];
+    for (i, o) in outputs.iter_mut().enumerate() {
+        if i > 0 {
+            line.literal(" ")?;
+        }
+        *o = read_pattern(&mut line)?;
+    }
+    line.end()?;
+
+    let entry = Entry { patterns, outputs };
+    if let Err(e) = entry.decode() {
+        return Err(line.error_at(1, format!("a consistent display ({})", e)));
+    }
+    Ok(entry)
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Vec<Entry>;
+    type Answer1 = usize;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<Entry>, ParseError> {
+        parse::lines(input).map(read_entry).collect()
+    }
+
+    fn part1(entries: &Vec<Entry>) -> usize {
+        entries
+            .iter()
+            .flat_map(|e| e.outputs.iter())
+            .filter(|o| matches!(o.count_ones(), 2 | 3 | 4 | 7))
+            .count()
+    }
+
+    fn part2(entries: &Vec<Entry>) -> u32 {
+        // Every entry was checked to decode while parsing.
+        entries.iter().map(|e| e.decode().unwrap()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = "\
+be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
+fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
+fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
+aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
+fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
+dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
+bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
+egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
+gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce
+";
+
+    #[test]
+    fn test_deduce() {
+        let entries = Day8::parse(
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf",
+        )
+        .unwrap();
+        let wiring = entries[0].deduce().unwrap();
+        assert_eq!("a->c b->f c->g d->a e->b f->d g->e", wiring.to_string());
+        assert_eq!(Ok(5353), entries[0].decode());
+
+        let mut broken = entries[0].clone();
+        broken.patterns[4] = bit('a') | bit('b');
+        assert_eq!(Err(WiringError::NoUniquePattern(2)), broken.deduce());
+        let mut broken = entries[0].clone();
+        broken.patterns[0] = bit('a') | bit('b') | bit('c');
+        assert_eq!(Err(WiringError::AmbiguousWire('d')), broken.deduce());
+    }
+
+    #[test]
+    fn test_broken_entry() {
+        let input = format!("{}ab ab ab ab ab ab ab ab ab ab | ab ab ab ab\n", INPUT);
+        let err = Day8::parse(&input).unwrap_err();
+        assert_eq!((11, 1), (err.line, err.column));
+        assert_eq!(
+            "a consistent display (no unique pattern with 2 wires)",
+            err.expected
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(26, Day8::part1(&Day8::parse(INPUT).unwrap()));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(61229, Day8::part2(&Day8::parse(INPUT).unwrap()));
+    }
+}
@@ -0,0 +1,135 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::iter::Iterator;
+use std::vec::Vec;
+
+use crate::grid::{Coord, Grid};
+use crate::parse::ParseError;
+use crate::Solution;
+
+/// A region of the map that drains into a single low point.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Basin {
+    pub low_point: Coord,
+    pub cells: Vec<Coord>,
+}
+
+impl Basin {
+    pub fn size(&self) -> usize {
+        self.cells.len()
+    }
+}
+
+pub struct Heightmap {
+    heights: Grid<u8>,
+}
+
+impl Heightmap {
+    fn risk(&self, pos: Coord) -> Option<u8> {
+        let h = self.heights[pos];
+        for (_, n) in self.heights.neighbors4(pos) {
+            if *n <= h {
+                return None;
+            }
+        }
+        Some(1 + h)
+    }
+
+    /// Finds the basin around every low point. Basins are bounded by
+    /// cells of height 9, which belong to no basin.
+    pub fn basins(&self) -> Vec<Basin> {
+        self.heights
+            .coords()
+            .filter(|pos| self.risk(*pos).is_some())
+            .map(|pos| self.basin(pos))
+            .collect()
+    }
+
+    fn basin(&self, low_point: Coord) -> Basin {
+        let mut seen = HashSet::from([low_point]);
+        let mut stack = vec![low_point];
+        let mut cells = Vec::new();
+        while let Some(cell) = stack.pop() {
+            cells.push(cell);
+            for (n, h) in self.heights.neighbors4(cell) {
+                if *h < 9 && seen.insert(n) {
+                    stack.push(n);
+                }
+            }
+        }
+        cells.sort_unstable();
+        Basin { low_point, cells }
+    }
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Heightmap;
+    type Answer1 = u64;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Heightmap, ParseError> {
+        Ok(Heightmap {
+            heights: Grid::parse_digits(input)?,
+        })
+    }
+
+    fn part1(map: &Heightmap) -> u64 {
+        map.heights
+            .coords()
+            .map(|pos| map.risk(pos).unwrap_or(0) as u64)
+            .sum()
+    }
+
+    fn part2(map: &Heightmap) -> usize {
+        let mut sizes: Vec<usize> = map.basins().iter().map(Basin::size).collect();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes.iter().take(3).product()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = "\
+2199943210
+3987894921
+9856789892
+8767896789
+9899965678
+";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(15, Day9::part1(&Day9::parse(INPUT).unwrap()));
+    }
+
+    #[test]
+    fn test_basins() {
+        let basins = Day9::parse(INPUT).unwrap().basins();
+        let sizes: Vec<_> = basins.iter().map(Basin::size).collect();
+        assert_eq!(vec![3, 9, 14, 9], sizes);
+        assert_eq!((0, 1), basins[0].low_point);
+        assert_eq!(vec![(0, 0), (0, 1), (1, 0)], basins[0].cells);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(1134, Day9::part2(&Day9::parse(INPUT).unwrap()));
+    }
+}
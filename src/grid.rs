@@ -0,0 +1,291 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A dense two-dimensional grid, as used by the many puzzles whose input is
+//! a block of characters.
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::parse::{self, ParseError};
+
+/// A `(row, column)` position in a [`Grid`].
+pub type Coord = (usize, usize);
+
+const OFFSETS4: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+const OFFSETS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// A rectangular grid of cells stored in row-major order.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from cells in row-major order.
+    ///
+    /// Panics if `cells` does not hold exactly `rows * cols` cells.
+    pub fn from_vec(rows: usize, cols: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(rows * cols, cells.len());
+        Grid { cells, rows, cols }
+    }
+
+    pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(Coord) -> T) -> Grid<T> {
+        let mut cells = Vec::with_capacity(rows * cols);
+        for r in 0..rows {
+            for c in 0..cols {
+                cells.push(f((r, c)));
+            }
+        }
+        Grid { cells, rows, cols }
+    }
+
+    pub fn new(rows: usize, cols: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_vec(rows, cols, vec![fill; rows * cols])
+    }
+
+    /// Parses a block of lines with one cell per character. `cell` returns
+    /// `None` for characters that are not valid cells, which are reported
+    /// as `expected`.
+    pub fn parse_with(
+        input: &str,
+        expected: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Grid<T>, ParseError> {
+        let mut cells = Vec::new();
+        let mut rows = 0;
+        let mut cols = 0;
+        for mut line in parse::lines(input) {
+            while !line.is_empty() {
+                let col = line.column();
+                let ch = line.next_char(expected)?;
+                cells.push(cell(ch).ok_or_else(|| line.error_at(col, expected))?);
+            }
+            let len = line.column() - 1;
+            if rows == 0 {
+                cols = len;
+            } else if len != cols {
+                return Err(line.error(format!("a row of {} cells", cols)));
+            }
+            rows += 1;
+        }
+        if rows == 0 || cols == 0 {
+            return Err(parse::eof_error(input, "a grid"));
+        }
+        Ok(Grid { cells, rows, cols })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, (r, c): Coord) -> Option<&T> {
+        if r < self.rows && c < self.cols {
+            Some(&self.cells[r * self.cols + c])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (r, c): Coord) -> Option<&mut T> {
+        if r < self.rows && c < self.cols {
+            Some(&mut self.cells[r * self.cols + c])
+        } else {
+            None
+        }
+    }
+
+    /// Moves from `pos` by `(dr, dc)`, returning `None` if that leaves the
+    /// grid.
+    pub fn offset(&self, (r, c): Coord, (dr, dc): (isize, isize)) -> Option<Coord> {
+        let r = r.checked_add_signed(dr)?;
+        let c = c.checked_add_signed(dc)?;
+        if r < self.rows && c < self.cols {
+            Some((r, c))
+        } else {
+            None
+        }
+    }
+
+    /// All coordinates in row-major order.
+    pub fn coords(&self) -> impl Iterator<Item = Coord> {
+        let cols = self.cols;
+        (0..self.rows).flat_map(move |r| (0..cols).map(move |c| (r, c)))
+    }
+
+    /// All cells with their coordinates, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.coords().zip(self.cells.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Coord, &mut T)> {
+        self.coords().zip(self.cells.iter_mut())
+    }
+
+    fn neighbors<'a>(
+        &'a self,
+        pos: Coord,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (Coord, &'a T)> {
+        offsets
+            .iter()
+            .filter_map(move |off| self.offset(pos, *off))
+            .map(move |n| (n, &self[n]))
+    }
+
+    /// The orthogonally adjacent cells of `pos`.
+    pub fn neighbors4(&self, pos: Coord) -> impl Iterator<Item = (Coord, &T)> {
+        self.neighbors(pos, &OFFSETS4)
+    }
+
+    /// The orthogonally and diagonally adjacent cells of `pos`.
+    pub fn neighbors8(&self, pos: Coord) -> impl Iterator<Item = (Coord, &T)> {
+        self.neighbors(pos, &OFFSETS8)
+    }
+
+    pub fn row(&self, r: usize) -> &[T] {
+        assert!(r < self.rows, "row {} out of bounds", r);
+        &self.cells[r * self.cols..(r + 1) * self.cols]
+    }
+
+    pub fn column(&self, c: usize) -> impl Iterator<Item = &T> {
+        assert!(c < self.cols, "column {} out of bounds", c);
+        self.cells.iter().skip(c).step_by(self.cols)
+    }
+
+    /// All rows in order. A grid without columns still has its rows, which
+    /// are empty.
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.rows).map(move |r| self.row(r))
+    }
+
+    pub fn iter_cols(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.cols).map(move |c| self.column(c))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.cols, self.rows, |(r, c)| self[(c, r)].clone())
+    }
+}
+
+impl Grid<u8> {
+    /// Parses a block of decimal digits.
+    pub fn parse_digits(input: &str) -> Result<Grid<u8>, ParseError> {
+        Grid::parse_with(input, "a digit", |c| c.to_digit(10).map(|d| d as u8))
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (r, c): Coord) -> &T {
+        assert!(r < self.rows && c < self.cols, "{:?} out of bounds", (r, c));
+        &self.cells[r * self.cols + c]
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, (r, c): Coord) -> &mut T {
+        assert!(r < self.rows && c < self.cols, "{:?} out of bounds", (r, c));
+        &mut self.cells[r * self.cols + c]
+    }
+}
+
+/// Prints one line per row, with the cells of a row printed back to back.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.iter_rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_display() {
+        let input = "123\n456\n";
+        let grid = Grid::parse_digits(input).unwrap();
+        assert_eq!((2, 3), (grid.rows(), grid.cols()));
+        assert_eq!(6, grid[(1, 2)]);
+        assert_eq!(None, grid.get((2, 0)));
+        assert_eq!(input, grid.to_string());
+        assert_eq!("14\n25\n36\n", grid.transpose().to_string());
+
+        let err = Grid::parse_digits("12\n3x\n").unwrap_err();
+        assert_eq!((2, 2), (err.line, err.column));
+        let err = Grid::parse_digits("12\n345\n").unwrap_err();
+        assert_eq!((2, 4), (err.line, err.column));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::parse_digits("123\n456\n789\n").unwrap();
+        let n4: Vec<_> = grid.neighbors4((0, 0)).map(|(_, v)| *v).collect();
+        assert_eq!(vec![4, 2], n4);
+        let n8: Vec<_> = grid.neighbors8((1, 1)).map(|(_, v)| *v).collect();
+        assert_eq!(vec![1, 2, 3, 4, 6, 7, 8, 9], n8);
+        let col: Vec<_> = grid.column(1).copied().collect();
+        assert_eq!(vec![2, 5, 8], col);
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_eq!("", Grid::new(0, 0, 0).to_string());
+        assert_eq!("\n\n", Grid::new(2, 0, 0).to_string());
+        assert_eq!(0, Grid::new(0, 3, 0).iter_cols().flatten().count());
+    }
+
+    #[test]
+    #[should_panic(expected = "column 4 out of bounds")]
+    fn test_column_out_of_bounds() {
+        Grid::new(3, 3, 0).column(4).count();
+    }
+}
@@ -11,3 +11,116 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
+
+//! Solutions for the 2021 Advent of Code, one module per day.
+//!
+//! Every solved day is listed in a registry so the `aoc` binary can look up
+//! and run any of them by number.
+
+pub mod bits;
+pub mod cuboid;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod grid;
+pub mod parse;
+pub mod vec3;
+
+use std::fmt;
+
+pub use parse::ParseError;
+
+/// A day's solution, split into a parsing stage and the two puzzle parts,
+/// which both work on the parsed input.
+pub trait Solution {
+    type Input;
+    type Answer1: fmt::Display;
+    type Answer2: fmt::Display;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Self::Answer1;
+    fn part2(input: &Self::Input) -> Self::Answer2;
+}
+
+/// A type-erased [`Solution`], so that days with different input and answer
+/// types can live in one registry. It takes the raw puzzle input and
+/// produces the answers as they should be printed.
+pub trait Puzzle: Sync {
+    /// Solves part 1 or part 2 of the puzzle, or both in order if `part` is
+    /// `None`, parsing the input only once.
+    fn run(&self, input: &str, part: Option<u32>) -> Result<Vec<String>, ParseError>;
+}
+
+impl<S: Solution + Sync> Puzzle for S {
+    fn run(&self, input: &str, part: Option<u32>) -> Result<Vec<String>, ParseError> {
+        let input = S::parse(input)?;
+        let mut answers = Vec::new();
+        if part != Some(2) {
+            answers.push(S::part1(&input).to_string());
+        }
+        if part != Some(1) {
+            answers.push(S::part2(&input).to_string());
+        }
+        Ok(answers)
+    }
+}
+
+static PUZZLES: &[(u32, &dyn Puzzle)] = &[
+    (1, &day1::Day1),
+    (2, &day2::Day2),
+    (3, &day3::Day3),
+    (4, &day4::Day4),
+    (5, &day5::Day5),
+    (6, &day6::Day6),
+    (7, &day7::Day7),
+    (8, &day8::Day8),
+    (9, &day9::Day9),
+    (10, &day10::Day10),
+    (11, &day11::Day11),
+    (12, &day12::Day12),
+    (13, &day13::Day13),
+    (14, &day14::Day14),
+    (15, &day15::Day15),
+    (16, &day16::Day16),
+    (17, &day17::Day17),
+    (18, &day18::Day18),
+    (19, &day19::Day19),
+    (20, &day20::Day20),
+    (21, &day21::Day21),
+    (22, &day22::Day22),
+    (23, &day23::Day23),
+    (24, &day24::Day24),
+    (25, &day25::Day25),
+];
+
+/// Looks up the puzzle for `day`, if it has been solved.
+pub fn puzzle(day: u32) -> Option<&'static dyn Puzzle> {
+    PUZZLES.iter().find(|(d, _)| *d == day).map(|(_, p)| *p)
+}
+
+/// Iterates over all solved days in order, along with their puzzles.
+pub fn puzzles() -> impl Iterator<Item = (u32, &'static dyn Puzzle)> {
+    PUZZLES.iter().copied()
+}
@@ -0,0 +1,239 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Line-oriented parsing helpers whose errors point at the offending input.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// An error in the puzzle input.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    /// What was expected at that position, e.g. "a number" or "'->'".
+    pub expected: String,
+    /// The full text of the offending line.
+    pub text: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )?;
+        writeln!(f, "    {}", self.text)?;
+        write!(f, "    {:>1$}", "^", self.column)
+    }
+}
+
+impl Error for ParseError {}
+
+/// A cursor over a single line of input.
+#[derive(Clone, Debug)]
+pub struct Cursor<'a> {
+    line: usize,
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    /// Creates a cursor at the start of `text`, which is line number `line`
+    /// (1-based) of the input.
+    pub fn new(line: usize, text: &'a str) -> Cursor<'a> {
+        Cursor { line, text, pos: 0 }
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The whole line, including the already consumed part.
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// The not yet consumed part of the line.
+    pub fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pos == self.text.len()
+    }
+
+    /// The 1-based column of the next character.
+    pub fn column(&self) -> usize {
+        self.text[..self.pos].chars().count() + 1
+    }
+
+    /// Creates an error at the cursor's position.
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        self.error_at(self.column(), expected)
+    }
+
+    /// Creates an error at the given 1-based column of this line.
+    pub fn error_at(&self, column: usize, expected: impl Into<String>) -> ParseError {
+        ParseError {
+            line: self.line,
+            column,
+            expected: expected.into(),
+            text: self.text.to_string(),
+        }
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    /// Consumes and returns the next character.
+    pub fn next_char(&mut self, expected: &str) -> Result<char, ParseError> {
+        let c = self.peek().ok_or_else(|| self.error(expected))?;
+        self.pos += c.len_utf8();
+        Ok(c)
+    }
+
+    /// Consumes the longest prefix whose characters all satisfy `pred`.
+    pub fn take_while(&mut self, mut pred: impl FnMut(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let len = rest.find(|c| !pred(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    pub fn skip_whitespace(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+
+    /// Consumes `lit`, which must come next.
+    pub fn literal(&mut self, lit: &str) -> Result<(), ParseError> {
+        if self.rest().starts_with(lit) {
+            self.pos += lit.len();
+            Ok(())
+        } else {
+            Err(self.error(format!("'{}'", lit)))
+        }
+    }
+
+    /// Consumes everything up to and including the next occurrence of
+    /// `pat`, and returns whether there was one. If not, consumes the rest
+    /// of the line.
+    pub fn skip_past(&mut self, pat: &str) -> bool {
+        match self.rest().find(pat) {
+            Some(i) => {
+                self.pos += i + pat.len();
+                true
+            }
+            None => {
+                self.pos = self.text.len();
+                false
+            }
+        }
+    }
+
+    /// Consumes a non-empty run of non-whitespace characters.
+    pub fn word(&mut self, expected: &str) -> Result<&'a str, ParseError> {
+        let word = self.take_while(|c| !c.is_whitespace());
+        if word.is_empty() {
+            Err(self.error(expected))
+        } else {
+            Ok(word)
+        }
+    }
+
+    /// Consumes an optionally signed decimal integer.
+    pub fn number<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        let start = self.clone();
+        if let Some('-' | '+') = self.peek() {
+            self.pos += 1;
+        }
+        let digits = self.take_while(|c| c.is_ascii_digit());
+        let text = &start.rest()[..self.pos - start.pos];
+        if digits.is_empty() {
+            *self = start;
+            return Err(self.error("a number"));
+        }
+        text.parse().map_err(|_| {
+            let err = start.error("a number in range");
+            *self = start;
+            err
+        })
+    }
+
+    /// Parses one or more items separated by `sep`.
+    pub fn separated<T>(
+        &mut self,
+        sep: char,
+        mut item: impl FnMut(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        let mut items = vec![item(self)?];
+        while self.peek() == Some(sep) {
+            self.pos += sep.len_utf8();
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+
+    /// Checks that the whole line has been consumed.
+    pub fn end(&self) -> Result<(), ParseError> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error("end of line"))
+        }
+    }
+}
+
+/// Iterates over the lines of `input`, each wrapped in a [`Cursor`].
+pub fn lines(input: &str) -> impl Iterator<Item = Cursor<'_>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, text)| Cursor::new(i + 1, text))
+}
+
+/// Returns an error located just past the last line of `input`, for input
+/// that ends too early.
+pub fn eof_error(input: &str, expected: impl Into<String>) -> ParseError {
+    Cursor::new(input.lines().count() + 1, "").error(expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_number() {
+        let mut c = Cursor::new(1, "-12,x");
+        assert_eq!(Ok(-12), c.number::<i32>());
+        assert_eq!(Ok(()), c.literal(","));
+        let err = c.number::<i32>().unwrap_err();
+        assert_eq!((1, 5), (err.line, err.column));
+        assert_eq!("a number", err.expected);
+    }
+
+    #[test]
+    fn test_error_display() {
+        let err = Cursor::new(3, "up x").error_at(4, "a number");
+        assert_eq!(
+            "line 3, column 4: expected a number\n    up x\n       ^",
+            err.to_string()
+        );
+    }
+}
@@ -0,0 +1,166 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integer vectors in three dimensions, and the 24 rotations that map the
+//! coordinate axes onto each other.
+
+use std::fmt;
+use std::iter::Iterator;
+use std::ops::{Add, Neg, Sub};
+
+use crate::parse::{Cursor, ParseError};
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Vec3 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl Vec3 {
+    pub const ZERO: Vec3 = Vec3 { x: 0, y: 0, z: 0 };
+
+    pub fn new(x: i32, y: i32, z: i32) -> Vec3 {
+        Vec3 { x, y, z }
+    }
+
+    fn axis(self, i: usize) -> i32 {
+        [self.x, self.y, self.z][i]
+    }
+
+    /// The sum of the absolute values of the components.
+    pub fn manhattan(self) -> i32 {
+        self.x.abs() + self.y.abs() + self.z.abs()
+    }
+
+    pub fn length_squared(self) -> i32 {
+        self.x * self.x + self.y * self.y + self.z * self.z
+    }
+
+    /// Reads a vector written as `x,y,z`.
+    pub fn parse(line: &mut Cursor) -> Result<Vec3, ParseError> {
+        let x = line.number()?;
+        line.literal(",")?;
+        let y = line.number()?;
+        line.literal(",")?;
+        let z = line.number()?;
+        Ok(Vec3 { x, y, z })
+    }
+}
+
+impl Add for Vec3 {
+    type Output = Vec3;
+
+    fn add(self, rhs: Vec3) -> Vec3 {
+        Vec3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for Vec3 {
+    type Output = Vec3;
+
+    fn sub(self, rhs: Vec3) -> Vec3 {
+        Vec3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Neg for Vec3 {
+    type Output = Vec3;
+
+    fn neg(self) -> Vec3 {
+        Vec3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl fmt::Display for Vec3 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+/// A rotation that maps every axis onto a possibly negated axis. Component
+/// `i` of a rotated vector is component `perm[i]` of the original, times
+/// `sign[i]`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Rotation {
+    perm: [usize; 3],
+    sign: [i32; 3],
+}
+
+impl Rotation {
+    pub const IDENTITY: Rotation = Rotation {
+        perm: [0, 1, 2],
+        sign: [1, 1, 1],
+    };
+
+    /// All 24 rotations, starting with the identity. Odd permutations need
+    /// an odd number of negated axes to not be reflections, and even ones
+    /// an even number.
+    pub fn all() -> impl Iterator<Item = Rotation> {
+        const PERMS: [([usize; 3], i32); 6] = [
+            ([0, 1, 2], 1),
+            ([1, 2, 0], 1),
+            ([2, 0, 1], 1),
+            ([0, 2, 1], -1),
+            ([2, 1, 0], -1),
+            ([1, 0, 2], -1),
+        ];
+        PERMS.into_iter().flat_map(|(perm, parity)| {
+            (0..8).filter_map(move |negated| {
+                let sign = [0, 1, 2].map(|i| if negated & (1 << i) != 0 { -1 } else { 1 });
+                (sign[0] * sign[1] * sign[2] == parity).then_some(Rotation { perm, sign })
+            })
+        })
+    }
+
+    pub fn apply(self, v: Vec3) -> Vec3 {
+        let c = |i: usize| self.sign[i] * v.axis(self.perm[i]);
+        Vec3::new(c(0), c(1), c(2))
+    }
+}
+
+/// Prints where each component of a rotated vector comes from, e.g.
+/// `-y,x,z` for a quarter turn about the z axis.
+impl fmt::Display for Rotation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for i in 0..3 {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            if self.sign[i] < 0 {
+                write!(f, "-")?;
+            }
+            write!(f, "{}", ['x', 'y', 'z'][self.perm[i]])?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_rotations() {
+        let v = Vec3::new(1, 2, 3);
+        let images: HashSet<_> = Rotation::all().map(|r| r.apply(v)).collect();
+        assert_eq!(24, images.len());
+        assert!(images.contains(&Vec3::new(-2, 1, 3)));
+        assert!(!images.contains(&Vec3::new(-1, 2, 3)));
+        assert_eq!(Some(Rotation::IDENTITY), Rotation::all().next());
+        assert_eq!("x,-y,-z", Rotation::all().nth(3).unwrap().to_string());
+    }
+}